@@ -1,6 +1,7 @@
 // src/components/DaoSettings.js
 import React, { useState } from "react";
 import { FaTools, FaCoins, FaClock, FaSave, FaTimes } from "react-icons/fa";
+import { providers, utils } from "near-api-js";
 
 const DaoSettings = ({ selector, accountId, contractId }) => {
   const [minDeposit, setMinDeposit] = useState("");
@@ -9,6 +10,17 @@ const DaoSettings = ({ selector, accountId, contractId }) => {
 
   const handleSubmit = async () => {
     try {
+      // Der Proposal-Bond wird bei der Finalisierung erstattet, Überschuss sofort
+      const provider = new providers.JsonRpcProvider("https://rpc.testnet.near.org");
+      const configRes = await provider.query({
+        request_type: "call_function",
+        account_id: contractId,
+        method_name: "get_config",
+        args_base64: Buffer.from(JSON.stringify({})).toString("base64"),
+        finality: "optimistic",
+      });
+      const config = JSON.parse(new TextDecoder().decode(new Uint8Array(configRes.result)));
+
       const wallet = await selector.wallet();
       await wallet.signAndSendTransaction({
         signerId: accountId,
@@ -18,17 +30,20 @@ const DaoSettings = ({ selector, accountId, contractId }) => {
             type: "FunctionCall",
             params: {
               methodName: "update_settings",
+              // Erstellt ein UpdateConfig-Proposal; wirksam erst nach Abstimmung und Ausführung
               args: {
-                min_proposal_deposit: minDeposit,
-                voting_duration_sec: votingDuration,
+                update: {
+                  proposal_bond: minDeposit ? utils.format.parseNearAmount(minDeposit) : null,
+                  voting_period_sec: votingDuration ? Number(votingDuration) : null,
+                },
               },
               gas: "30000000000000",
-              deposit: "1",
+              deposit: config.proposal_bond,
             },
           },
         ],
       });
-      setStatus("✅ Einstellungen aktualisiert.");
+      setStatus("✅ Proposal zur Änderung der Einstellungen erstellt.");
     } catch (err) {
       console.error("❌ Fehler beim Aktualisieren:", err);
       setStatus("❌ Fehler beim Speichern.");
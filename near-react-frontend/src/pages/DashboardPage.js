@@ -26,8 +26,6 @@ import { BASENAME } from "../App";
 import { providers } from "near-api-js";
 
 const API_URL = process.env.REACT_APP_API_URL || "";
-const TREASURY_ACCOUNT = "treasury.dao.lioneluser.testnet";
-const TEAM_ACCOUNT = "team.dao.lioneluser.testnet";
 
 const DashboardPage = ({
   accountId,
@@ -140,12 +138,21 @@ const DashboardPage = ({
   useEffect(() => {
     const fetchSpecialBalances = async () => {
       const provider = new providers.JsonRpcProvider("https://rpc.testnet.near.org");
+      // Treasury- und Team-Account aus der Contract-Konfiguration
+      const configRes = await provider.query({
+        request_type: "call_function",
+        account_id: contractId,
+        method_name: "get_config",
+        args_base64: Buffer.from(JSON.stringify({})).toString("base64"),
+        finality: "optimistic",
+      });
+      const config = JSON.parse(new TextDecoder().decode(new Uint8Array(configRes.result)));
       // Treasury
       const treasuryRes = await provider.query({
         request_type: "call_function",
         account_id: contractId,
         method_name: "ft_balance_of",
-        args_base64: Buffer.from(JSON.stringify({ account_id: TREASURY_ACCOUNT })).toString("base64"),
+        args_base64: Buffer.from(JSON.stringify({ account_id: config.treasury_account_id })).toString("base64"),
         finality: "optimistic",
       });
       const decodedTreasury = new TextDecoder().decode(new Uint8Array(treasuryRes.result));
@@ -155,7 +162,7 @@ const DashboardPage = ({
         request_type: "call_function",
         account_id: contractId,
         method_name: "ft_balance_of",
-        args_base64: Buffer.from(JSON.stringify({ account_id: TEAM_ACCOUNT })).toString("base64"),
+        args_base64: Buffer.from(JSON.stringify({ account_id: config.team_account_id })).toString("base64"),
         finality: "optimistic",
       });
       const decodedTeam = new TextDecoder().decode(new Uint8Array(teamRes.result));
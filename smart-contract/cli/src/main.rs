@@ -0,0 +1,427 @@
+mod output;
+
+use anyhow::{anyhow, Result};
+use clap::{Args, Parser, Subcommand};
+use near_workspaces::types::{NearToken, SecretKey};
+use near_workspaces::{Account, AccountId};
+use thesis_dao_client::{CreateProposalArgs, DaoClient, Proposal, ProposalKind, U128};
+
+use crate::output::{done, print, Format};
+
+#[derive(Parser)]
+#[command(name = "thesis-dao", about = "Admin tool for THESIS DAO operations")]
+struct Cli {
+    /// RPC-Endpunkt (Sandbox, Testnet, Mainnet oder eigener Node)
+    #[arg(long, env = "THESIS_DAO_RPC", default_value = "https://rpc.testnet.near.org")]
+    rpc_url: String,
+    /// Account des DAO-Contracts
+    #[arg(long, env = "THESIS_DAO_CONTRACT")]
+    contract: AccountId,
+    #[command(flatten)]
+    signer: SignerArgs,
+    #[arg(long, value_enum, default_value = "table")]
+    output: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct SignerArgs {
+    /// Signierender Account (für Change-Methoden, bei Views optional)
+    #[arg(long, env = "THESIS_DAO_ACCOUNT")]
+    account: Option<AccountId>,
+    /// Private Key des Accounts (ed25519:...)
+    #[arg(long, env = "THESIS_DAO_SECRET_KEY", conflicts_with = "credentials")]
+    secret_key: Option<String>,
+    /// Credentials-Datei im Format von near-cli (~/.near-credentials/...)
+    #[arg(long)]
+    credentials: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(subcommand)]
+    Proposal(ProposalCommand),
+    #[command(subcommand)]
+    Role(RoleCommand),
+    #[command(subcommand)]
+    Team(TeamCommand),
+    /// Token-Guthaben aller Accounts oder eines einzelnen Accounts
+    Balances { account_id: Option<AccountId> },
+    #[command(subcommand)]
+    Dividends(DividendsCommand),
+}
+
+#[derive(Args)]
+struct ProposalCreateArgs {
+    #[arg(long)]
+    title: String,
+    #[arg(long)]
+    description: String,
+    #[arg(long)]
+    link: Option<String>,
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    /// Token-Betrag in yocto (Transfer-Proposals)
+    #[arg(long)]
+    amount: Option<u128>,
+    #[arg(long)]
+    target_account: Option<String>,
+    #[arg(long)]
+    category: Option<String>,
+    /// Abstimmungsende (Unix-Zeit in Sekunden)
+    #[arg(long)]
+    deadline: Option<u64>,
+    #[arg(long)]
+    required_role: Option<String>,
+    #[arg(long)]
+    quorum: Option<u128>,
+    /// ProposalKind als JSON, z.B. '{"RemoveRole":{"role":"finance"}}'
+    #[arg(long)]
+    kind: Option<String>,
+    /// Proposal-Bond in NEAR
+    #[arg(long, default_value = "0")]
+    bond: String,
+}
+
+#[derive(Subcommand)]
+enum ProposalCommand {
+    Create(Box<ProposalCreateArgs>),
+    List,
+    Show { proposal_id: u64 },
+    Vote {
+        proposal_id: u64,
+        /// Gegenstimme statt Zustimmung
+        #[arg(long)]
+        against: bool,
+    },
+    Finalize { proposal_id: u64 },
+    Execute { proposal_id: u64 },
+}
+
+#[derive(Subcommand)]
+enum RoleCommand {
+    Assign {
+        account_id: AccountId,
+        role: String,
+        /// Ablauf der Rolle (Unix-Zeit in ms)
+        #[arg(long)]
+        expires_at: Option<u64>,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    List,
+}
+
+#[derive(Subcommand)]
+enum TeamCommand {
+    Add { account_id: AccountId },
+    Remove { account_id: AccountId },
+    List,
+}
+
+#[derive(Subcommand)]
+enum DividendsCommand {
+    /// Schüttet den Betrag (in NEAR) anteilig an alle Token-Halter aus
+    Distribute { amount: String },
+}
+
+/// "1.5" -> 1.5 NEAR in yocto
+fn parse_near(amount: &str) -> Result<NearToken> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if fraction.len() > 24 {
+        return Err(anyhow!("Too many decimals in {}", amount));
+    }
+    let whole: u128 = if whole.is_empty() { 0 } else { whole.parse()? };
+    let fraction: u128 = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<24}", fraction).parse()?
+    };
+    whole
+        .checked_mul(10u128.pow(24))
+        .and_then(|yocto| yocto.checked_add(fraction))
+        .map(NearToken::from_yoctonear)
+        .ok_or_else(|| anyhow!("Amount {} is too large", amount))
+}
+
+fn proposal_row(proposal: &Proposal) -> Vec<String> {
+    let votes = |votes: &[(String, u128)]| votes.iter().map(|(_, weight)| weight).sum::<u128>().to_string();
+    vec![
+        proposal.id.to_string(),
+        proposal.title.clone(),
+        format!("{:?}", proposal.status),
+        votes(&proposal.votes_for),
+        votes(&proposal.votes_against),
+        proposal.executed.to_string(),
+    ]
+}
+
+const PROPOSAL_HEADERS: [&str; 6] = ["ID", "TITLE", "STATUS", "FOR", "AGAINST", "EXECUTED"];
+
+async fn signer(cli: &Cli) -> Result<Account> {
+    // Beliebiger RPC-Endpunkt; `custom` ist in near-workspaces nur mit dem Feature `unstable` verfügbar
+    let worker = near_workspaces::testnet().rpc_addr(&cli.rpc_url).await?;
+    if let Some(path) = &cli.signer.credentials {
+        return Ok(Account::from_file(path, &worker)?);
+    }
+    // Ohne Key reicht ein beliebiger Account-Name für Views
+    let account_id = cli.signer.account.clone().unwrap_or_else(|| cli.contract.clone());
+    let secret_key = match &cli.signer.secret_key {
+        Some(secret_key) => secret_key.parse::<SecretKey>()?,
+        None => SecretKey::from_random(near_workspaces::types::KeyType::ED25519),
+    };
+    Ok(Account::from_secret_key(account_id, secret_key, &worker))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dao = DaoClient::new(signer(&cli).await?, cli.contract.clone());
+    let format = cli.output;
+
+    match cli.command {
+        Command::Proposal(command) => match command {
+            ProposalCommand::Create(create) => {
+                let ProposalCreateArgs {
+                    title,
+                    description,
+                    link,
+                    tags,
+                    amount,
+                    target_account,
+                    category,
+                    deadline,
+                    required_role,
+                    quorum,
+                    kind,
+                    bond,
+                } = *create;
+                let kind = kind
+                    .map(|kind| serde_json::from_str::<ProposalKind>(&kind))
+                    .transpose()?;
+                let args = CreateProposalArgs {
+                    title,
+                    description,
+                    link,
+                    tags,
+                    amount: amount.map(U128),
+                    target_account,
+                    category,
+                    deadline,
+                    required_role,
+                    quorum: quorum.map(U128),
+                    kind,
+                };
+                let proposal_id = dao.create_proposal(&args, parse_near(&bond)?).await?;
+                print(format, &proposal_id, &["PROPOSAL ID"], vec![vec![proposal_id.to_string()]])?;
+            }
+            ProposalCommand::List => {
+                let proposals = dao.get_proposals().await?;
+                let rows = proposals.iter().map(proposal_row).collect();
+                print(format, &proposals, &PROPOSAL_HEADERS, rows)?;
+            }
+            ProposalCommand::Show { proposal_id } => {
+                let proposal = dao
+                    .get_proposal_by_id(proposal_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Proposal {} not found", proposal_id))?;
+                let rows = vec![
+                    vec!["id".to_string(), proposal.id.to_string()],
+                    vec!["title".to_string(), proposal.title.clone()],
+                    vec!["description".to_string(), proposal.description.clone()],
+                    vec!["proposer".to_string(), proposal.proposer.clone()],
+                    vec!["kind".to_string(), serde_json::to_string(&proposal.kind)?],
+                    vec!["status".to_string(), format!("{:?}", proposal.status)],
+                    vec!["deadline".to_string(), format!("{:?}", proposal.deadline)],
+                    vec!["quorum".to_string(), format!("{:?}", proposal.quorum)],
+                    vec!["votes_for".to_string(), format!("{:?}", proposal.votes_for)],
+                    vec!["votes_against".to_string(), format!("{:?}", proposal.votes_against)],
+                    vec!["executed".to_string(), proposal.executed.to_string()],
+                ];
+                print(format, &proposal, &["FIELD", "VALUE"], rows)?;
+            }
+            ProposalCommand::Vote { proposal_id, against } => {
+                dao.vote_on_proposal(proposal_id, !against).await?;
+                done(format, &format!("Voted {} proposal {}", if against { "against" } else { "for" }, proposal_id));
+            }
+            ProposalCommand::Finalize { proposal_id } => {
+                dao.finalize_proposal(proposal_id).await?;
+                let proposal = dao.get_proposal_by_id(proposal_id).await?;
+                let rows = proposal.iter().map(proposal_row).collect();
+                print(format, &proposal, &PROPOSAL_HEADERS, rows)?;
+            }
+            ProposalCommand::Execute { proposal_id } => {
+                dao.execute_proposal(proposal_id).await?;
+                done(format, &format!("Executed (or approved) proposal {}", proposal_id));
+            }
+        },
+        Command::Role(command) => match command {
+            RoleCommand::Assign { account_id, role, expires_at, reason } => {
+                dao.assign_role(&account_id, &role, expires_at, reason.as_deref()).await?;
+                done(format, &format!("Assigned {} to {}", role, account_id));
+            }
+            RoleCommand::List => {
+                let roles = dao.get_all_roles().await?;
+                let rows = roles
+                    .iter()
+                    .map(|(account_id, role)| vec![account_id.to_string(), role.clone()])
+                    .collect();
+                print(format, &roles, &["ACCOUNT", "ROLE"], rows)?;
+            }
+        },
+        Command::Team(command) => match command {
+            TeamCommand::Add { account_id } => {
+                dao.add_team_member(&account_id).await?;
+                done(format, &format!("Added {} to the team", account_id));
+            }
+            TeamCommand::Remove { account_id } => {
+                dao.remove_team_member(&account_id).await?;
+                done(format, &format!("Removed {} from the team", account_id));
+            }
+            TeamCommand::List => {
+                let team = dao.get_team_accounts().await?;
+                let rows = team.iter().map(|account_id| vec![account_id.to_string()]).collect();
+                print(format, &team, &["ACCOUNT"], rows)?;
+            }
+        },
+        Command::Balances { account_id } => {
+            let balances = match account_id {
+                Some(account_id) => {
+                    let balance = dao.ft_balance_of(&account_id).await?;
+                    vec![(account_id, U128(balance.as_yoctonear()))]
+                }
+                None => dao.get_all_balances().await?,
+            };
+            let rows = balances
+                .iter()
+                .map(|(account_id, balance)| vec![account_id.to_string(), balance.0.to_string()])
+                .collect();
+            print(format, &balances, &["ACCOUNT", "BALANCE (yocto)"], rows)?;
+        }
+        Command::Dividends(DividendsCommand::Distribute { amount }) => {
+            let amount = parse_near(&amount)?;
+            dao.distribute_dividends(amount).await?;
+            done(format, &format!("Distributed (or approved) {} yoctoNEAR in dividends", amount.as_yoctonear()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use thesis_dao_client::ProposalStatus;
+
+    fn parse(args: &[&str]) -> Cli {
+        let base = ["thesis-dao", "--contract", "dao.testnet"];
+        Cli::try_parse_from(base.iter().chain(args)).unwrap()
+    }
+
+    #[test]
+    fn parse_near_converts_decimals_to_yocto() {
+        assert_eq!(parse_near("1").unwrap().as_yoctonear(), 10u128.pow(24));
+        assert_eq!(parse_near("1.5").unwrap().as_yoctonear(), 15 * 10u128.pow(23));
+        assert_eq!(parse_near(".25").unwrap().as_yoctonear(), 25 * 10u128.pow(22));
+        assert_eq!(parse_near("0").unwrap().as_yoctonear(), 0);
+        assert_eq!(parse_near("0.000000000000000000000001").unwrap().as_yoctonear(), 1);
+    }
+
+    #[test]
+    fn parse_near_rejects_invalid_amounts() {
+        assert!(parse_near("0.0000000000000000000000001").is_err());
+        assert!(parse_near("abc").is_err());
+        assert!(parse_near("-1").is_err());
+        assert!(parse_near("1000000000000000000").is_err());
+    }
+
+    #[test]
+    fn parses_global_options() {
+        let cli = parse(&["--output", "json", "--account", "alice.testnet", "proposal", "list"]);
+        assert_eq!(cli.contract.as_str(), "dao.testnet");
+        assert_eq!(cli.rpc_url, "https://rpc.testnet.near.org");
+        assert!(cli.output == Format::Json);
+        assert_eq!(cli.signer.account.unwrap().as_str(), "alice.testnet");
+        assert!(matches!(cli.command, Command::Proposal(ProposalCommand::List)));
+    }
+
+    #[test]
+    fn parses_proposal_create() {
+        let cli = parse(&[
+            "proposal", "create", "--title", "Budget", "--description", "Q3", "--tag", "finance", "--tag", "q3",
+            "--amount", "100", "--kind", r#"{"RemoveRole":{"role":"finance"}}"#,
+        ]);
+        assert!(cli.output == Format::Table);
+        match cli.command {
+            Command::Proposal(ProposalCommand::Create(create)) => {
+                assert_eq!(create.title, "Budget");
+                assert_eq!(create.tags, vec!["finance", "q3"]);
+                assert_eq!(create.amount, Some(100));
+                assert_eq!(create.bond, "0");
+                assert_eq!(create.deadline, None);
+                assert!(serde_json::from_str::<ProposalKind>(&create.kind.unwrap()).is_ok());
+            }
+            _ => panic!("expected proposal create"),
+        }
+    }
+
+    #[test]
+    fn parses_vote_and_role_commands() {
+        match parse(&["proposal", "vote", "7", "--against"]).command {
+            Command::Proposal(ProposalCommand::Vote { proposal_id, against }) => {
+                assert_eq!(proposal_id, 7);
+                assert!(against);
+            }
+            _ => panic!("expected proposal vote"),
+        }
+        match parse(&["role", "assign", "bob.testnet", "finance", "--expires-at", "1700000000000"]).command {
+            Command::Role(RoleCommand::Assign { account_id, role, expires_at, reason }) => {
+                assert_eq!(account_id.as_str(), "bob.testnet");
+                assert_eq!(role, "finance");
+                assert_eq!(expires_at, Some(1_700_000_000_000));
+                assert_eq!(reason, None);
+            }
+            _ => panic!("expected role assign"),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let base = ["thesis-dao", "--contract", "dao.testnet"];
+        let try_parse = |args: &[&str]| Cli::try_parse_from(base.iter().chain(args)).is_err();
+        assert!(try_parse(&["proposal", "vote", "not-a-number"]));
+        assert!(try_parse(&["team", "add", "Invalid Account"]));
+        assert!(try_parse(&["--output", "xml", "proposal", "list"]));
+        assert!(try_parse(&["--secret-key", "ed25519:abc", "--credentials", "key.json", "proposal", "list"]));
+        assert!(Cli::try_parse_from(["thesis-dao", "proposal", "list"]).is_err());
+    }
+
+    #[test]
+    fn proposal_row_sums_vote_weights() {
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": 3,
+            "title": "Budget",
+            "description": "",
+            "created_at": 0,
+            "proposer": "alice.testnet",
+            "executed": false,
+            "votes_for": [["alice.testnet", 100], ["bob.testnet", 50]],
+            "votes_against": [["carol.testnet", 20]],
+            "voted_accounts": [],
+            "status": "Accepted",
+            "link": null,
+            "tags": [],
+            "amount": null,
+            "target_account": null,
+            "category": null,
+            "deadline": null,
+            "required_role": null,
+            "quorum": null,
+            "kind": "Transfer",
+            "bond": "0"
+        }))
+        .unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Accepted);
+        assert_eq!(proposal_row(&proposal), vec!["3", "Budget", "Accepted", "150", "20", "false"]);
+    }
+}
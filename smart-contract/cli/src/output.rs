@@ -0,0 +1,83 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(ValueEnum, Clone, Copy, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+/// Gibt `value` als JSON oder als Tabelle (Kopfzeile + Zeilen) aus
+pub fn print<T: Serialize>(format: Format, value: &T, headers: &[&str], rows: Vec<Vec<String>>) -> anyhow::Result<()> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        Format::Table => print!("{}", render_table(headers, &rows)),
+    }
+    Ok(())
+}
+
+/// Erfolgsmeldung für Change-Methoden ohne Rückgabewert
+pub fn done(format: Format, message: &str) {
+    match format {
+        Format::Json => println!("{}", serde_json::json!({ "ok": true, "message": message })),
+        Format::Table => println!("{}", message),
+    }
+}
+
+/// Spalten auf die breiteste Zelle ausgerichtet, getrennt durch zwei Leerzeichen
+fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    let mut table = format!("{}\n{}\n", line(headers.to_vec()), line(separator.iter().map(String::as_str).collect()));
+    for row in rows {
+        table.push_str(&line(row.iter().map(String::as_str).collect()));
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_table_aligns_columns() {
+        let rows = vec![
+            vec!["alice.testnet".to_string(), "core".to_string()],
+            vec!["bob.testnet".to_string(), "finance".to_string()],
+        ];
+        assert_eq!(
+            render_table(&["ACCOUNT", "ROLE"], &rows),
+            "ACCOUNT        ROLE\n\
+             -------------  -------\n\
+             alice.testnet  core\n\
+             bob.testnet    finance\n"
+        );
+    }
+
+    #[test]
+    fn render_table_without_rows_prints_headers() {
+        assert_eq!(render_table(&["ID", "TITLE"], &[]), "ID  TITLE\n--  -----\n");
+    }
+
+    #[test]
+    fn render_table_counts_unicode_chars() {
+        let rows = vec![vec!["Prüfung".to_string(), "1".to_string()]];
+        assert_eq!(render_table(&["TITLE", "ID"], &rows), "TITLE    ID\n-------  --\nPrüfung  1\n");
+    }
+}
@@ -0,0 +1,616 @@
+//! Typisierter Client für den THESIS DAO Contract. Verwendet die Typen des Contracts
+//! (`Proposal`, `FungibleTokenMetadata`, `StorageBalance`, ...) und ruft Views und
+//! Change-Methoden über einen `near_workspaces::Account` auf (Sandbox, Testnet oder Mainnet).
+
+use near_workspaces::result::ExecutionFinalResult;
+use near_workspaces::types::NearToken;
+use near_workspaces::{Account, AccountId, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+pub use hello_near::activity::ActivityRecord;
+pub use hello_near::allowances::{Allowance, FinanceSpendRecord};
+pub use hello_near::bounties::Bounty;
+pub use hello_near::budgets::Budget;
+pub use hello_near::config::{ConfigUpdate, DaoConfig};
+pub use hello_near::elections::Election;
+pub use hello_near::errors::{DaoError, ErrorCodeInfo};
+pub use hello_near::grants::Grant;
+pub use hello_near::membership::MembershipApplication;
+pub use hello_near::metadata::FungibleTokenMetadata;
+pub use hello_near::migration::v1_role_holders;
+pub use hello_near::multisig::PendingAction;
+pub use hello_near::pause::Subsystem;
+pub use hello_near::proposal::{Proposal, ProposalKind, ProposalStatus};
+pub use hello_near::roles::{Permission, RoleChange, RoleGrant};
+pub use hello_near::storage::{StorageBalance, StorageBalanceBounds};
+pub use hello_near::streams::PaymentStream;
+pub use hello_near::treasury::Asset;
+pub use hello_near::upgrade::{ScheduledUpgrade, UpgradeStatus};
+pub use near_sdk::json_types::{Base58CryptoHash, U128};
+
+const ONE_YOCTO: NearToken = NearToken::from_yoctonear(1);
+const NO_DEPOSIT: NearToken = NearToken::from_yoctonear(0);
+
+/// Argumente für `create_proposal`; nicht gesetzte Felder übernimmt der Contract aus der DaoConfig
+#[derive(Serialize, Default, Clone)]
+pub struct CreateProposalArgs {
+    pub title: String,
+    pub description: String,
+    pub link: Option<String>,
+    pub tags: Vec<String>,
+    pub amount: Option<U128>,
+    pub target_account: Option<String>,
+    pub category: Option<String>,
+    pub deadline: Option<u64>,
+    pub required_role: Option<String>,
+    pub quorum: Option<U128>,
+    pub kind: Option<ProposalKind>,
+}
+
+/// Client für einen deployten DAO-Contract; Change-Methoden werden mit `signer` signiert
+#[derive(Clone)]
+pub struct DaoClient {
+    contract_id: AccountId,
+    signer: Account,
+}
+
+impl DaoClient {
+    pub fn new(signer: Account, contract_id: AccountId) -> Self {
+        Self { contract_id, signer }
+    }
+
+    /// Gleicher Contract, anderer Signer
+    pub fn with_signer(&self, signer: Account) -> Self {
+        Self::new(signer, self.contract_id.clone())
+    }
+
+    pub fn contract_id(&self) -> &AccountId {
+        &self.contract_id
+    }
+
+    pub fn signer(&self) -> &Account {
+        &self.signer
+    }
+
+    async fn view<T: DeserializeOwned>(&self, method: &str, args: Value) -> Result<T> {
+        self.signer.view(&self.contract_id, method).args_json(args).await?.json()
+    }
+
+    async fn transact(&self, method: &str, args: Value, deposit: NearToken) -> Result<ExecutionFinalResult> {
+        self.signer
+            .call(&self.contract_id, method)
+            .args_json(args)
+            .deposit(deposit)
+            .max_gas()
+            .transact()
+            .await
+    }
+
+    /// Change-Methode mit Rückgabewert; schlägt fehl, falls die Ausführung fehlschlägt
+    async fn call<T: DeserializeOwned>(&self, method: &str, args: Value, deposit: NearToken) -> Result<T> {
+        self.transact(method, args, deposit).await?.into_result()?.json()
+    }
+
+    /// Change-Methode ohne Rückgabewert
+    async fn call_unit(&self, method: &str, args: Value, deposit: NearToken) -> Result<()> {
+        self.transact(method, args, deposit).await?.into_result()?;
+        Ok(())
+    }
+
+    // =====================
+    // Initialisierung (Signer = Contract-Account)
+    // =====================
+
+    pub async fn new_default_meta(
+        &self,
+        owner_id: &AccountId,
+        total_supply: U128,
+        treasury_account_id: &AccountId,
+        team_account_id: &AccountId,
+    ) -> Result<()> {
+        let args = json!({
+            "owner_id": owner_id,
+            "total_supply": total_supply,
+            "treasury_account_id": treasury_account_id,
+            "team_account_id": team_account_id,
+        });
+        self.call_unit("new_default_meta", args, NO_DEPOSIT).await
+    }
+
+    /// Contract-Methode `new` (eigene Metadaten)
+    pub async fn new_with_metadata(
+        &self,
+        owner_id: &AccountId,
+        total_supply: U128,
+        metadata: &FungibleTokenMetadata,
+        treasury_account_id: &AccountId,
+        team_account_id: &AccountId,
+    ) -> Result<()> {
+        let args = json!({
+            "owner_id": owner_id,
+            "total_supply": total_supply,
+            "metadata": metadata,
+            "treasury_account_id": treasury_account_id,
+            "team_account_id": team_account_id,
+        });
+        self.call_unit("new", args, NO_DEPOSIT).await
+    }
+
+    // =====================
+    // NEP-141 / Storage / Metadata
+    // =====================
+
+    pub async fn ft_transfer(&self, receiver_id: &AccountId, amount: NearToken, memo: Option<&str>) -> Result<()> {
+        let args = json!({ "receiver_id": receiver_id, "amount": amount, "memo": memo });
+        self.call_unit("ft_transfer", args, ONE_YOCTO).await
+    }
+
+    pub async fn ft_transfer_call(
+        &self,
+        receiver_id: &AccountId,
+        amount: NearToken,
+        memo: Option<&str>,
+        msg: &str,
+    ) -> Result<NearToken> {
+        let args = json!({ "receiver_id": receiver_id, "amount": amount, "memo": memo, "msg": msg });
+        self.call("ft_transfer_call", args, ONE_YOCTO).await
+    }
+
+    pub async fn ft_total_supply(&self) -> Result<U128> {
+        self.view("ft_total_supply", json!({})).await
+    }
+
+    pub async fn ft_balance_of(&self, account_id: &AccountId) -> Result<NearToken> {
+        self.view("ft_balance_of", json!({ "account_id": account_id })).await
+    }
+
+    pub async fn ft_metadata(&self) -> Result<FungibleTokenMetadata> {
+        self.view("ft_metadata", json!({})).await
+    }
+
+    pub async fn storage_deposit(&self, account_id: Option<&AccountId>, deposit: NearToken) -> Result<StorageBalance> {
+        let args = json!({ "account_id": account_id, "registration_only": null });
+        self.call("storage_deposit", args, deposit).await
+    }
+
+    pub async fn storage_balance_bounds(&self) -> Result<StorageBalanceBounds> {
+        self.view("storage_balance_bounds", json!({})).await
+    }
+
+    pub async fn storage_balance_of(&self, account_id: &AccountId) -> Result<Option<StorageBalance>> {
+        self.view("storage_balance_of", json!({ "account_id": account_id })).await
+    }
+
+    // =====================
+    // Token-Verkauf, Supply, Guthaben
+    // =====================
+
+    pub async fn buy_tokens(&self, deposit: NearToken) -> Result<()> {
+        self.call_unit("buy_tokens", json!({}), deposit).await
+    }
+
+    pub async fn get_total_supply(&self) -> Result<U128> {
+        self.view("get_total_supply", json!({})).await
+    }
+
+    pub async fn get_token_pool(&self) -> Result<U128> {
+        self.view("get_token_pool", json!({})).await
+    }
+
+    pub async fn get_all_balances(&self) -> Result<Vec<(AccountId, U128)>> {
+        self.view("get_all_balances", json!({})).await
+    }
+
+    // =====================
+    // Proposals
+    // =====================
+
+    /// `bond` muss mindestens `DaoConfig::proposal_bond` entsprechen
+    pub async fn create_proposal(&self, args: &CreateProposalArgs, bond: NearToken) -> Result<u64> {
+        self.call("create_proposal", json!(args), bond).await
+    }
+
+    pub async fn get_proposals(&self) -> Result<Vec<Proposal>> {
+        self.view("get_proposals", json!({})).await
+    }
+
+    pub async fn get_proposal_by_id(&self, proposal_id: u64) -> Result<Option<Proposal>> {
+        self.view("get_proposal_by_id", json!({ "proposal_id": proposal_id })).await
+    }
+
+    pub async fn vote_on_proposal(&self, proposal_id: u64, support: bool) -> Result<()> {
+        let args = json!({ "proposal_id": proposal_id, "support": support });
+        self.call_unit("vote_on_proposal", args, ONE_YOCTO).await
+    }
+
+    pub async fn finalize_proposal(&self, proposal_id: u64) -> Result<()> {
+        self.call_unit("finalize_proposal", json!({ "proposal_id": proposal_id }), NO_DEPOSIT)
+            .await
+    }
+
+    pub async fn execute_proposal(&self, proposal_id: u64) -> Result<()> {
+        self.call_unit("execute_proposal", json!({ "proposal_id": proposal_id }), NO_DEPOSIT)
+            .await
+    }
+
+    // =====================
+    // Rollen und Team
+    // =====================
+
+    pub async fn assign_role(
+        &self,
+        account_id: &AccountId,
+        role: &str,
+        expires_at: Option<u64>,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let args = json!({ "account_id": account_id, "role": role, "expires_at": expires_at, "reason": reason });
+        self.call_unit("assign_role", args, NO_DEPOSIT).await
+    }
+
+    pub async fn revoke_role(&self, account_id: &AccountId, role: &str, reason: Option<&str>) -> Result<()> {
+        let args = json!({ "account_id": account_id, "role": role, "reason": reason });
+        self.call_unit("revoke_role", args, NO_DEPOSIT).await
+    }
+
+    pub async fn get_role(&self, account_id: &AccountId) -> Result<Option<String>> {
+        self.view("get_role", json!({ "account_id": account_id })).await
+    }
+
+    pub async fn get_all_roles(&self) -> Result<Vec<(AccountId, String)>> {
+        self.view("get_all_roles", json!({})).await
+    }
+
+    pub async fn get_roles(&self, account_id: &AccountId) -> Result<Vec<String>> {
+        self.view("get_roles", json!({ "account_id": account_id })).await
+    }
+
+    pub async fn get_role_grants(&self, account_id: &AccountId) -> Result<Vec<RoleGrant>> {
+        self.view("get_role_grants", json!({ "account_id": account_id })).await
+    }
+
+    pub async fn get_role_history(&self, from_index: Option<u64>, limit: Option<u64>) -> Result<Vec<RoleChange>> {
+        self.view("get_role_history", json!({ "from_index": from_index, "limit": limit }))
+            .await
+    }
+
+    pub async fn get_role_thresholds(&self) -> Result<Vec<(String, U128)>> {
+        self.view("get_role_thresholds", json!({})).await
+    }
+
+    pub async fn get_permission_matrix(&self) -> Result<Vec<(String, Vec<Permission>)>> {
+        self.view("get_permission_matrix", json!({})).await
+    }
+
+    pub async fn has_permission(&self, account_id: &AccountId, permission: Permission) -> Result<bool> {
+        self.view("has_permission", json!({ "account_id": account_id, "permission": permission }))
+            .await
+    }
+
+    pub async fn add_team_member(&self, account_id: &AccountId) -> Result<()> {
+        self.call_unit("add_team_member", json!({ "account_id": account_id }), NO_DEPOSIT)
+            .await
+    }
+
+    pub async fn remove_team_member(&self, account_id: &AccountId) -> Result<()> {
+        self.call_unit("remove_team_member", json!({ "account_id": account_id }), NO_DEPOSIT)
+            .await
+    }
+
+    pub async fn get_team_accounts(&self) -> Result<Vec<AccountId>> {
+        self.view("get_team_accounts", json!({})).await
+    }
+
+    // =====================
+    // Dividenden, Multisig und Notfall-Pause
+    // =====================
+
+    /// Schüttet `amount` NEAR anteilig an alle Token-Halter aus
+    pub async fn distribute_dividends(&self, amount: NearToken) -> Result<()> {
+        self.call_unit("distribute_dividends", json!({}), amount).await
+    }
+
+    pub async fn approve_action(&self, action_id: u64) -> Result<()> {
+        self.call_unit("approve_action", json!({ "action_id": action_id }), NO_DEPOSIT)
+            .await
+    }
+
+    pub async fn cancel_action(&self, action_id: u64) -> Result<()> {
+        self.call_unit("cancel_action", json!({ "action_id": action_id }), NO_DEPOSIT)
+            .await
+    }
+
+    pub async fn get_pending_actions(&self) -> Result<Vec<PendingAction>> {
+        self.view("get_pending_actions", json!({})).await
+    }
+
+    /// Guardians pausieren sofort, Core-Mitglieder reichen eine Freigabe ein
+    pub async fn pause(&self, subsystem: Subsystem) -> Result<()> {
+        self.call_unit("pause", json!({ "subsystem": subsystem }), NO_DEPOSIT).await
+    }
+
+    pub async fn paused(&self) -> Result<Vec<Subsystem>> {
+        self.view("paused", json!({})).await
+    }
+
+    // =====================
+    // Contract-Upgrade
+    // =====================
+
+    /// Hinterlegt den Wasm-Code (roh, nicht als JSON) und liefert dessen Hash für das UpgradeContract-Proposal.
+    /// Der Signer braucht die CreateProposal-Permission.
+    pub async fn stage_upgrade_code(&self, code: &[u8], deposit: NearToken) -> Result<Base58CryptoHash> {
+        self.signer
+            .call(&self.contract_id, "stage_upgrade_code")
+            .args(code.to_vec())
+            .deposit(deposit)
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()
+    }
+
+    pub async fn apply_upgrade(&self) -> Result<()> {
+        self.call_unit("apply_upgrade", json!({}), NO_DEPOSIT).await
+    }
+
+    pub async fn get_upgrade(&self) -> Result<Option<ScheduledUpgrade>> {
+        self.view("get_upgrade", json!({})).await
+    }
+
+    pub async fn get_error_codes(&self) -> Result<Vec<ErrorCodeInfo>> {
+        self.view("get_error_codes", json!({})).await
+    }
+
+    pub async fn get_error_description(&self, code: u32) -> Result<Option<String>> {
+        self.view("get_error_description", json!({ "code": code })).await
+    }
+
+    pub async fn get_state_version(&self) -> Result<u32> {
+        self.view("get_state_version", json!({})).await
+    }
+
+    pub async fn is_upgrade_code_staged(&self, code_hash: &Base58CryptoHash) -> Result<bool> {
+        self.view("is_upgrade_code_staged", json!({ "code_hash": code_hash })).await
+    }
+
+    // =====================
+    // Mitgliedschaft und Wahlen
+    // =====================
+
+    pub async fn apply_for_membership(
+        &self,
+        profile_hash: &str,
+        requested_role: &str,
+        deposit: NearToken,
+    ) -> Result<u64> {
+        let args = json!({ "profile_hash": profile_hash, "requested_role": requested_role });
+        self.call("apply_for_membership", args, deposit).await
+    }
+
+    pub async fn approve_application(&self, application_id: u64) -> Result<()> {
+        self.call_unit("approve_application", json!({ "application_id": application_id }), NO_DEPOSIT)
+            .await
+    }
+
+    pub async fn reject_application(&self, application_id: u64, reason: Option<&str>) -> Result<()> {
+        let args = json!({ "application_id": application_id, "reason": reason });
+        self.call_unit("reject_application", args, NO_DEPOSIT).await
+    }
+
+    pub async fn get_application(&self, application_id: u64) -> Result<Option<MembershipApplication>> {
+        self.view("get_application", json!({ "application_id": application_id }))
+            .await
+    }
+
+    pub async fn get_pending_applications(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Vec<MembershipApplication>> {
+        self.view("get_pending_applications", json!({ "from_index": from_index, "limit": limit }))
+            .await
+    }
+
+    pub async fn nominate(&self) -> Result<()> {
+        self.call_unit("nominate", json!({}), NO_DEPOSIT).await
+    }
+
+    pub async fn vote_in_election(&self, candidates: &[AccountId]) -> Result<()> {
+        self.call_unit("vote_in_election", json!({ "candidates": candidates }), ONE_YOCTO)
+            .await
+    }
+
+    pub async fn finalize_election(&self) -> Result<()> {
+        self.call_unit("finalize_election", json!({}), NO_DEPOSIT).await
+    }
+
+    pub async fn get_current_election(&self) -> Result<Option<Election>> {
+        self.view("get_current_election", json!({})).await
+    }
+
+    pub async fn get_candidates(&self, term_id: u64) -> Result<Vec<(AccountId, U128)>> {
+        self.view("get_candidates", json!({ "term_id": term_id })).await
+    }
+
+    pub async fn get_election_results(&self, term_id: u64) -> Result<Option<Vec<AccountId>>> {
+        self.view("get_election_results", json!({ "term_id": term_id })).await
+    }
+
+    pub async fn get_term_history(&self, from_index: Option<u64>, limit: Option<u64>) -> Result<Vec<Election>> {
+        self.view("get_term_history", json!({ "from_index": from_index, "limit": limit }))
+            .await
+    }
+
+    pub async fn get_council(&self) -> Result<Vec<AccountId>> {
+        self.view("get_council", json!({})).await
+    }
+
+    // =====================
+    // Konfiguration
+    // =====================
+
+    pub async fn get_config(&self) -> Result<DaoConfig> {
+        self.view("get_config", json!({})).await
+    }
+
+    /// Erstellt ein UpdateConfig-Proposal und gibt dessen ID zurück
+    pub async fn update_settings(&self, update: &ConfigUpdate, bond: NearToken) -> Result<u64> {
+        self.call("update_settings", json!({ "update": update }), bond).await
+    }
+
+    // =====================
+    // Treasury, Budgets, Allowances
+    // =====================
+
+    pub async fn donate(&self, amount: NearToken) -> Result<()> {
+        self.call_unit("donate", json!({}), amount).await
+    }
+
+    pub async fn get_spendable_near(&self) -> Result<U128> {
+        self.view("get_spendable_near", json!({})).await
+    }
+
+    pub async fn get_treasury_holdings(&self) -> Result<Vec<(Asset, U128)>> {
+        self.view("get_treasury_holdings", json!({})).await
+    }
+
+    pub async fn get_budgets(&self) -> Result<Vec<Budget>> {
+        self.view("get_budgets", json!({})).await
+    }
+
+    pub async fn get_budget(&self, category: &str, asset: &Asset) -> Result<Option<Budget>> {
+        self.view("get_budget", json!({ "category": category, "asset": asset })).await
+    }
+
+    pub async fn finance_spend(&self, recipient: &AccountId, amount: U128, memo: &str) -> Result<()> {
+        let args = json!({ "recipient": recipient, "amount": amount, "memo": memo });
+        self.call_unit("finance_spend", args, ONE_YOCTO).await
+    }
+
+    pub async fn get_allowance(&self, account_id: &AccountId) -> Result<Option<Allowance>> {
+        self.view("get_allowance", json!({ "account_id": account_id })).await
+    }
+
+    pub async fn get_allowances(&self) -> Result<Vec<Allowance>> {
+        self.view("get_allowances", json!({})).await
+    }
+
+    pub async fn get_finance_spends(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Vec<FinanceSpendRecord>> {
+        self.view("get_finance_spends", json!({ "from_index": from_index, "limit": limit }))
+            .await
+    }
+
+    // =====================
+    // Streams, Grants, Bounties
+    // =====================
+
+    pub async fn withdraw_stream(&self, stream_id: u64) -> Result<U128> {
+        self.call("withdraw_stream", json!({ "stream_id": stream_id }), NO_DEPOSIT)
+            .await
+    }
+
+    pub async fn get_stream(&self, stream_id: u64) -> Result<Option<PaymentStream>> {
+        self.view("get_stream", json!({ "stream_id": stream_id })).await
+    }
+
+    pub async fn get_active_streams(&self) -> Result<Vec<PaymentStream>> {
+        self.view("get_active_streams", json!({})).await
+    }
+
+    pub async fn get_stream_owed(&self, stream_id: u64) -> Result<U128> {
+        self.view("get_stream_owed", json!({ "stream_id": stream_id })).await
+    }
+
+    pub async fn submit_milestone(
+        &self,
+        grant_id: u64,
+        milestone_index: u32,
+        evidence_link: &str,
+        evidence_hash: &str,
+    ) -> Result<()> {
+        let args = json!({
+            "grant_id": grant_id,
+            "milestone_index": milestone_index,
+            "evidence_link": evidence_link,
+            "evidence_hash": evidence_hash,
+        });
+        self.call_unit("submit_milestone", args, NO_DEPOSIT).await
+    }
+
+    pub async fn release_milestone(&self, grant_id: u64, milestone_index: u32) -> Result<()> {
+        let args = json!({ "grant_id": grant_id, "milestone_index": milestone_index });
+        self.call_unit("release_milestone", args, NO_DEPOSIT).await
+    }
+
+    pub async fn get_grant(&self, grant_id: u64) -> Result<Option<Grant>> {
+        self.view("get_grant", json!({ "grant_id": grant_id })).await
+    }
+
+    pub async fn get_grants(&self, from_index: Option<u64>, limit: Option<u64>) -> Result<Vec<Grant>> {
+        self.view("get_grants", json!({ "from_index": from_index, "limit": limit }))
+            .await
+    }
+
+    pub async fn claim_bounty(&self, bounty_id: u64) -> Result<()> {
+        self.call_unit("claim_bounty", json!({ "bounty_id": bounty_id }), NO_DEPOSIT)
+            .await
+    }
+
+    pub async fn submit_bounty_done(&self, bounty_id: u64, proof: &str) -> Result<()> {
+        let args = json!({ "bounty_id": bounty_id, "proof": proof });
+        self.call_unit("submit_bounty_done", args, NO_DEPOSIT).await
+    }
+
+    pub async fn approve_bounty_claim(&self, bounty_id: u64, claimant: &AccountId) -> Result<()> {
+        let args = json!({ "bounty_id": bounty_id, "claimant": claimant });
+        self.call_unit("approve_bounty_claim", args, NO_DEPOSIT).await
+    }
+
+    pub async fn reject_bounty_claim(&self, bounty_id: u64, claimant: &AccountId) -> Result<()> {
+        let args = json!({ "bounty_id": bounty_id, "claimant": claimant });
+        self.call_unit("reject_bounty_claim", args, NO_DEPOSIT).await
+    }
+
+    pub async fn get_bounty(&self, bounty_id: u64) -> Result<Option<Bounty>> {
+        self.view("get_bounty", json!({ "bounty_id": bounty_id })).await
+    }
+
+    pub async fn get_open_bounties(&self) -> Result<Vec<Bounty>> {
+        self.view("get_open_bounties", json!({})).await
+    }
+
+    pub async fn get_claimed_bounties(&self, account_id: Option<&AccountId>) -> Result<Vec<Bounty>> {
+        self.view("get_claimed_bounties", json!({ "account_id": account_id })).await
+    }
+
+    // =====================
+    // Aktivitätsprotokoll
+    // =====================
+
+    pub async fn get_activity(&self, from_index: Option<u64>, limit: Option<u64>) -> Result<Vec<ActivityRecord>> {
+        self.view("get_activity", json!({ "from_index": from_index, "limit": limit }))
+            .await
+    }
+
+    pub async fn get_account_activity(
+        &self,
+        account_id: &AccountId,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Vec<ActivityRecord>> {
+        let args = json!({ "account_id": account_id, "from_index": from_index, "limit": limit });
+        self.view("get_account_activity", args).await
+    }
+
+    pub async fn get_activity_count(&self) -> Result<u64> {
+        self.view("get_activity_count", json!({})).await
+    }
+}
@@ -0,0 +1,208 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use near_workspaces::types::NearToken;
+use near_workspaces::{AccountId, Contract};
+use serde_json::{json, Value};
+use thesis_dao_client::{v1_role_holders, Asset, DaoClient, Proposal, ProposalKind, ProposalStatus, U128};
+
+const TOTAL_SUPPLY: u128 = 10_000_000 * 10u128.pow(24);
+/// Git-Revision mit dem v1-Layout (Contract unter smart-contract/)
+const V1_REVISION: &str = "c190d254";
+/// V1_REVISION enthält kein Cargo.lock; die Abhängigkeiten sind hier fixiert
+const V1_LOCKFILE: &str = include_str!("fixtures/thesis-dao-v1.Cargo.lock");
+
+/// Exportiert den Contract aus V1_REVISION in ein temporäres Verzeichnis und baut ihn reproduzierbar
+async fn compile_v1() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let repo = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let dir = std::env::temp_dir().join(format!("thesis-dao-v1-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let archive = dir.join("v1.tar");
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(&repo)
+        .args(["archive", "-o"])
+        .arg(&archive)
+        .args([V1_REVISION, "smart-contract"])
+        .status()?;
+    assert!(status.success(), "git archive {} failed", V1_REVISION);
+    let status = Command::new("tar").arg("-xf").arg(&archive).arg("-C").arg(&dir).status()?;
+    assert!(status.success(), "extracting {} failed", archive.display());
+    let project = dir.join("smart-contract");
+    std::fs::write(project.join("Cargo.lock"), V1_LOCKFILE)?;
+    Ok(near_workspaces::compile_project(project.to_str().unwrap()).await?)
+}
+
+/// Accounts mit Einträgen unter dem Roles-Prefix (v1-Strings wie v2-RoleGrants)
+async fn role_holders(contract: &Contract) -> Result<Vec<AccountId>, Box<dyn std::error::Error>> {
+    let state = contract.view_state().await?;
+    // Contract und near-workspaces nutzen verschiedene near-account-id-Versionen
+    Ok(v1_role_holders(state.keys().map(Vec::as_slice))
+        .iter()
+        .map(|account_id| account_id.as_str().parse())
+        .collect::<Result<_, _>>()?)
+}
+
+/// Deployt das v1-Layout, erzeugt Daten, deployt den aktuellen Code,
+/// ruft migrate() auf und prüft, dass Guthaben, Rollen, Team und Proposals erhalten bleiben.
+#[tokio::test]
+async fn test_migrate_from_v1_keeps_state() -> Result<(), Box<dyn std::error::Error>> {
+    let v1_wasm = compile_v1().await?;
+    let current_wasm = near_workspaces::compile_project(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).await?;
+    let sandbox = near_workspaces::sandbox().await?;
+    let contract = sandbox.dev_deploy(&v1_wasm).await?;
+    let owner = sandbox.dev_create_account().await?;
+    let alice = sandbox.dev_create_account().await?;
+    let bob = sandbox.dev_create_account().await?;
+    // Hat in v1 eine Rolle, ist aber weder registriert noch im Team
+    let carol = sandbox.dev_create_account().await?;
+
+    // --- Daten im v1-Layout erzeugen ---
+    contract
+        .call("new_default_meta")
+        .args_json(json!({ "owner_id": owner.id(), "total_supply": U128(TOTAL_SUPPLY) }))
+        .transact()
+        .await?
+        .into_result()?;
+    for buyer in [&alice, &bob] {
+        buyer
+            .call(contract.id(), "buy_tokens")
+            .deposit(NearToken::from_near(2))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+    for holder in [&bob, &carol] {
+        owner
+            .call(contract.id(), "assign_role")
+            .args_json(json!({ "account_id": holder.id(), "role": "finance" }))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+    owner
+        .call(contract.id(), "add_team_member")
+        .args_json(json!({ "account_id": alice.id() }))
+        .transact()
+        .await?
+        .into_result()?;
+    for title in ["Pay bob", "Still open"] {
+        alice
+            .call(contract.id(), "create_proposal")
+            .args_json(json!({
+                "title": title,
+                "description": "Created before the migration",
+                "tags": ["migration"],
+                "amount": U128(10u128.pow(24)),
+                "target_account": bob.id(),
+            }))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+    bob.call(contract.id(), "vote_on_proposal")
+        .args_json(json!({ "proposal_id": 0, "support": true }))
+        .deposit(NearToken::from_yoctonear(1))
+        .transact()
+        .await?
+        .into_result()?;
+    owner
+        .call(contract.id(), "finalize_proposal")
+        .args_json(json!({ "proposal_id": 0 }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let balances_v1: Vec<(String, U128)> = contract.view("get_all_balances").await?.json()?;
+    let roles_v1: Vec<(String, String)> = contract.view("get_all_roles").await?.json()?;
+    let team_v1: Vec<String> = contract.view("get_team_accounts").await?.json()?;
+    let proposals_v1: Vec<Value> = contract.view("get_proposals").await?.json()?;
+    let token_pool_v1: U128 = contract.view("get_token_pool").await?.json()?;
+    assert_eq!(proposals_v1.len(), 2);
+    assert!(!roles_v1.iter().any(|(account_id, _)| account_id == carol.id().as_str()));
+    let mut holders_v1 = role_holders(&contract).await?;
+    assert!(holders_v1.contains(carol.id()));
+
+    // --- Upgrade auf das aktuelle Layout ---
+    contract.as_account().deploy(&current_wasm).await?.into_result()?;
+    contract
+        .call("migrate")
+        .args_json(json!({ "role_holders": holders_v1 }))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let dao = DaoClient::new(owner.clone(), contract.id().clone());
+    assert_eq!(dao.get_state_version().await?, 2);
+    assert_eq!(dao.ft_total_supply().await?, U128(TOTAL_SUPPLY));
+    assert_eq!(dao.ft_metadata().await?.symbol, "THESISDAO");
+    assert_eq!(dao.get_token_pool().await?, token_pool_v1);
+    // Das in v1 gehaltene NEAR (u.a. 2 x 2 NEAR aus buy_tokens) steht jetzt im Treasury-Ledger
+    let near_holding = dao
+        .get_treasury_holdings()
+        .await?
+        .into_iter()
+        .find_map(|(asset, amount)| (asset == Asset::Near).then_some(amount.0))
+        .expect("NEAR is seeded into the treasury");
+    assert!(near_holding >= NearToken::from_near(4).as_yoctonear());
+    assert!(near_holding <= contract.view_account().await?.balance.as_yoctonear());
+
+    let balances: Vec<(String, U128)> = dao
+        .get_all_balances()
+        .await?
+        .into_iter()
+        .map(|(account_id, balance)| (account_id.to_string(), balance))
+        .collect();
+    assert_eq!(balances, balances_v1);
+
+    for (account_id, role) in &roles_v1 {
+        let roles = dao.get_roles(&account_id.parse()?).await?;
+        assert!(roles.contains(role), "{} lost role {}", account_id, role);
+    }
+    assert!(dao.get_roles(carol.id()).await?.contains(&"finance".to_string()));
+    // Jeder Eintrag unter dem Roles-Prefix ist jetzt im neuen Format lesbar
+    let mut holders = role_holders(&contract).await?;
+    holders.sort();
+    holders_v1.sort();
+    assert_eq!(holders, holders_v1);
+    for account_id in &holders {
+        assert!(!dao.get_role_grants(account_id).await?.is_empty());
+    }
+    let team: Vec<String> = dao.get_team_accounts().await?.iter().map(ToString::to_string).collect();
+    assert_eq!(team, team_v1);
+
+    let proposals: Vec<Proposal> = dao.get_proposals().await?;
+    assert_eq!(proposals.len(), proposals_v1.len());
+    for (proposal, old) in proposals.iter().zip(&proposals_v1) {
+        assert_eq!(proposal.id, old["id"].as_u64().unwrap());
+        assert_eq!(proposal.title, old["title"].as_str().unwrap());
+        assert_eq!(proposal.proposer, old["proposer"].as_str().unwrap());
+        assert_eq!(json!(proposal.votes_for), old["votes_for"]);
+        assert_eq!(json!(proposal.status), old["status"]);
+        assert_eq!(proposal.amount, Some(10u128.pow(24)));
+        assert_eq!(proposal.kind, ProposalKind::Transfer);
+    }
+    assert_eq!(proposals[0].status, ProposalStatus::Accepted);
+
+    // Neue Funktionen arbeiten auf dem migrierten State
+    dao.execute_proposal(0).await?;
+    assert!(dao.get_proposal_by_id(0).await?.expect("proposal exists").executed);
+    let next_id = dao
+        .create_proposal(
+            &thesis_dao_client::CreateProposalArgs {
+                title: "After migration".to_string(),
+                description: "Created after the migration".to_string(),
+                ..Default::default()
+            },
+            NearToken::from_near(1),
+        )
+        .await?;
+    assert_eq!(next_id, 2);
+
+    // Ein zweiter migrate()-Aufruf auf aktuellem State ändert nichts
+    contract.call("migrate").max_gas().transact().await?.into_result()?;
+    assert_eq!(dao.get_state_version().await?, 2);
+    assert_eq!(dao.get_proposals().await?.len(), 3);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use near_workspaces::types::NearToken;
+use thesis_dao_client::{CreateProposalArgs, DaoClient, DaoError, ProposalStatus, U128};
+
+const TOTAL_SUPPLY: u128 = 10_000_000 * 10u128.pow(24);
+
+#[tokio::test]
+async fn test_proposal_lifecycle_via_client() -> Result<(), Box<dyn std::error::Error>> {
+    let contract_wasm = near_workspaces::compile_project(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).await?;
+    let sandbox = near_workspaces::sandbox().await?;
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+    let owner = sandbox.dev_create_account().await?;
+    let treasury = sandbox.dev_create_account().await?;
+    let team = sandbox.dev_create_account().await?;
+    let alice = sandbox.dev_create_account().await?;
+
+    DaoClient::new(contract.as_account().clone(), contract.id().clone())
+        .new_default_meta(owner.id(), U128(TOTAL_SUPPLY), treasury.id(), team.id())
+        .await?;
+
+    let dao = DaoClient::new(owner.clone(), contract.id().clone());
+    assert_eq!(dao.ft_total_supply().await?, U128(TOTAL_SUPPLY));
+    assert_eq!(dao.ft_metadata().await?.symbol, "THESISDAO");
+    assert_eq!(dao.get_role(owner.id()).await?.as_deref(), Some("core"));
+    assert_eq!(dao.get_config().await?.treasury_account_id.as_str(), treasury.id().as_str());
+
+    // Token-Verkauf
+    let alice_dao = dao.with_signer(alice.clone());
+    alice_dao.buy_tokens(NearToken::from_near(1)).await?;
+    assert!(dao.ft_balance_of(alice.id()).await?.as_yoctonear() > 0);
+
+    // Transfer-Proposal aus der Treasury an Alice
+    let amount = 1_000 * 10u128.pow(24);
+    let proposal_id = dao
+        .create_proposal(
+            &CreateProposalArgs {
+                title: "Pay alice".to_string(),
+                description: "Sandbox test".to_string(),
+                amount: Some(U128(amount)),
+                target_account: Some(alice.id().to_string()),
+                ..Default::default()
+            },
+            NearToken::from_near(1),
+        )
+        .await?;
+    let before = dao.ft_balance_of(alice.id()).await?.as_yoctonear();
+
+    dao.with_signer(team.clone()).vote_on_proposal(proposal_id, true).await?;
+    dao.finalize_proposal(proposal_id).await?;
+    let proposal = dao.get_proposal_by_id(proposal_id).await?.expect("proposal exists");
+    assert_eq!(proposal.status, ProposalStatus::Accepted);
+
+    dao.execute_proposal(proposal_id).await?;
+    assert!(dao.get_proposal_by_id(proposal_id).await?.expect("proposal exists").executed);
+    assert_eq!(dao.ft_balance_of(alice.id()).await?.as_yoctonear(), before + amount);
+
+    // Nicht berechtigte Aufrufe schlagen fehl
+    let err = alice_dao.add_team_member(alice.id()).await.unwrap_err();
+    assert_eq!(DaoError::from_panic_message(&err.to_string()), Some(DaoError::MissingPermission));
+    let err = dao.vote_on_proposal(proposal_id, true).await.unwrap_err();
+    assert_eq!(DaoError::from_panic_message(&err.to_string()), Some(DaoError::ProposalAlreadyFinalized));
+    assert_eq!(
+        dao.get_error_description(DaoError::AlreadyVoted.code()).await?.as_deref(),
+        Some("You have already voted")
+    );
+
+    assert!(dao.get_activity_count().await? > 0);
+    Ok(())
+}
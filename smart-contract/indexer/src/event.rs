@@ -0,0 +1,367 @@
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+pub const EVENT_LOG_PREFIX: &str = "EVENT_JSON:";
+
+/// Rohes NEP-297 Event wie von `NearEvent::emit` geloggt
+#[derive(Deserialize, Debug, Clone)]
+pub struct RawEvent {
+    pub standard: String,
+    pub version: String,
+    pub event: String,
+    #[serde(default)]
+    pub data: Value,
+}
+
+/// Liest ein Event aus einer Log-Zeile; andere Logs werden ignoriert
+pub fn parse_log(log: &str) -> Option<RawEvent> {
+    let json = log.strip_prefix(EVENT_LOG_PREFIX)?;
+    serde_json::from_str(json).ok()
+}
+
+/// u128-Beträge kommen als String (U128/NearToken), ältere Logs teilweise als Zahl
+pub fn de_u128<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::String(s) => s.parse().map_err(serde::de::Error::custom),
+        Value::Number(n) => n
+            .as_u64()
+            .map(u128::from)
+            .ok_or_else(|| serde::de::Error::custom("invalid amount")),
+        other => Err(serde::de::Error::custom(format!("invalid amount: {}", other))),
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FtMint {
+    pub owner_id: String,
+    #[serde(deserialize_with = "de_u128")]
+    pub amount: u128,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FtTransfer {
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+    #[serde(deserialize_with = "de_u128")]
+    pub amount: u128,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: String,
+    pub title: String,
+    pub kind: Value,
+    pub deadline: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProposalVoted {
+    pub proposal_id: u64,
+    pub voter: String,
+    pub support: bool,
+    #[serde(deserialize_with = "de_u128")]
+    pub weight: u128,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProposalFinalized {
+    pub proposal_id: u64,
+    pub status: String,
+    #[serde(deserialize_with = "de_u128")]
+    pub votes_for: u128,
+    #[serde(deserialize_with = "de_u128")]
+    pub votes_against: u128,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+}
+
+/// Endgültiges Ergebnis einer Treasury-Auszahlung (Executed oder Failed)
+#[derive(Deserialize, Debug)]
+pub struct ProposalPayoutResolved {
+    pub proposal_id: u64,
+    pub status: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RoleChanged {
+    pub account_id: String,
+    pub role: String,
+    pub granted: bool,
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamChanged {
+    pub account_id: String,
+    pub added: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DividendsDistributed {
+    #[serde(deserialize_with = "de_u128")]
+    pub total_amount: u128,
+    #[serde(deserialize_with = "de_u128")]
+    pub total_supply: u128,
+    pub recipients: u32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ActionApproved {
+    pub action_id: u64,
+    pub action: Value,
+    pub approvals: u32,
+    pub executed: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ActionCancelled {
+    pub action_id: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ApplicationSubmitted {
+    pub application_id: u64,
+    pub applicant: String,
+    pub requested_role: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ApplicationDecided {
+    pub application_id: u64,
+    pub status: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CandidateNominated {
+    pub term_id: u64,
+    pub candidate: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ElectionVoted {
+    pub term_id: u64,
+    pub voter: String,
+    pub candidates: Vec<String>,
+    #[serde(deserialize_with = "de_u128")]
+    pub weight: u128,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ElectionFinalized {
+    pub term_id: u64,
+    pub winners: Vec<String>,
+    pub council: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StreamCreated {
+    pub id: u64,
+    pub recipient: String,
+    pub asset: Value,
+    #[serde(deserialize_with = "de_u128")]
+    pub rate_per_sec: u128,
+    pub start: u64,
+    pub end: u64,
+    pub cliff: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StreamWithdrawn {
+    pub stream_id: u64,
+    #[serde(deserialize_with = "de_u128")]
+    pub amount: u128,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StreamCancelled {
+    pub stream_id: u64,
+    pub cancelled_at: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GrantMilestone {
+    pub description: String,
+    #[serde(deserialize_with = "de_u128")]
+    pub amount: u128,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GrantCreated {
+    pub id: u64,
+    pub grantee: String,
+    pub asset: Value,
+    pub milestones: Vec<GrantMilestone>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MilestoneSubmitted {
+    pub grant_id: u64,
+    pub milestone_index: u32,
+    pub evidence_link: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MilestoneReleased {
+    pub grant_id: u64,
+    pub milestone_index: u32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GrantCancelled {
+    pub grant_id: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BountyCreated {
+    pub id: u64,
+    pub description: String,
+    pub asset: Value,
+    #[serde(deserialize_with = "de_u128")]
+    pub amount: u128,
+    pub max_claims: u32,
+    pub deadline: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BountyClaimed {
+    pub bounty_id: u64,
+    pub account_id: String,
+    pub expires_at: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BountySubmitted {
+    pub bounty_id: u64,
+    pub account_id: String,
+    pub proof: String,
+}
+
+/// Genehmigung (nach erfolgter Auszahlung) oder Ablehnung einer Einreichung
+#[derive(Deserialize, Debug)]
+pub struct BountyClaimReviewed {
+    pub bounty_id: u64,
+    pub claimant: String,
+    pub approved: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BountyCancelled {
+    pub bounty_id: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TreasuryDeposit {
+    pub asset: Value,
+    pub sender: String,
+    #[serde(deserialize_with = "de_u128")]
+    pub amount: u128,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpgradeCodeStaged {
+    pub code_hash: String,
+    pub staged_by: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpgradeStatusChanged {
+    pub proposal_id: u64,
+    pub code_hash: String,
+    pub status: String,
+}
+
+/// Events, aus denen der Zustand materialisiert wird. Unbekannte Events werden nur
+/// im Rohprotokoll gespeichert.
+#[derive(Debug)]
+pub enum DaoEvent {
+    FtMint(Vec<FtMint>),
+    FtTransfer(Vec<FtTransfer>),
+    ProposalCreated(Vec<ProposalCreated>),
+    ProposalVoted(Vec<ProposalVoted>),
+    ProposalFinalized(Vec<ProposalFinalized>),
+    ProposalExecuted(Vec<ProposalExecuted>),
+    ProposalPayoutResolved(Vec<ProposalPayoutResolved>),
+    RoleChanged(Vec<RoleChanged>),
+    TeamChanged(Vec<TeamChanged>),
+    DividendsDistributed(Vec<DividendsDistributed>),
+    ActionApproved(Vec<ActionApproved>),
+    ActionCancelled(Vec<ActionCancelled>),
+    ApplicationSubmitted(Vec<ApplicationSubmitted>),
+    ApplicationDecided(Vec<ApplicationDecided>),
+    CandidateNominated(Vec<CandidateNominated>),
+    ElectionVoted(Vec<ElectionVoted>),
+    ElectionFinalized(Vec<ElectionFinalized>),
+    StreamCreated(Vec<StreamCreated>),
+    StreamWithdrawn(Vec<StreamWithdrawn>),
+    StreamCancelled(Vec<StreamCancelled>),
+    GrantCreated(Vec<GrantCreated>),
+    MilestoneSubmitted(Vec<MilestoneSubmitted>),
+    MilestoneReleased(Vec<MilestoneReleased>),
+    GrantCancelled(Vec<GrantCancelled>),
+    BountyCreated(Vec<BountyCreated>),
+    BountyClaimed(Vec<BountyClaimed>),
+    BountySubmitted(Vec<BountySubmitted>),
+    BountyClaimReviewed(Vec<BountyClaimReviewed>),
+    BountyCancelled(Vec<BountyCancelled>),
+    TreasuryDeposit(Vec<TreasuryDeposit>),
+    UpgradeCodeStaged(Vec<UpgradeCodeStaged>),
+    UpgradeStatusChanged(Vec<UpgradeStatusChanged>),
+    Other,
+}
+
+impl DaoEvent {
+    pub fn from_raw(raw: &RawEvent) -> anyhow::Result<Self> {
+        let data = raw.data.clone();
+        Ok(match (raw.standard.as_str(), raw.event.as_str()) {
+            ("nep141", "ft_mint") => DaoEvent::FtMint(serde_json::from_value(data)?),
+            ("nep141", "ft_transfer") => DaoEvent::FtTransfer(serde_json::from_value(data)?),
+            ("thesis_dao", "proposal_created") => DaoEvent::ProposalCreated(serde_json::from_value(data)?),
+            ("thesis_dao", "proposal_voted") => DaoEvent::ProposalVoted(serde_json::from_value(data)?),
+            ("thesis_dao", "proposal_finalized") => DaoEvent::ProposalFinalized(serde_json::from_value(data)?),
+            ("thesis_dao", "proposal_executed") => DaoEvent::ProposalExecuted(serde_json::from_value(data)?),
+            ("thesis_dao", "proposal_payout_resolved") => {
+                DaoEvent::ProposalPayoutResolved(serde_json::from_value(data)?)
+            }
+            ("thesis_dao", "role_changed") => DaoEvent::RoleChanged(serde_json::from_value(data)?),
+            ("thesis_dao", "team_changed") => DaoEvent::TeamChanged(serde_json::from_value(data)?),
+            ("thesis_dao", "dividends_distributed") => {
+                DaoEvent::DividendsDistributed(serde_json::from_value(data)?)
+            }
+            ("thesis_dao", "action_approved") => DaoEvent::ActionApproved(serde_json::from_value(data)?),
+            ("thesis_dao", "action_cancelled") => DaoEvent::ActionCancelled(serde_json::from_value(data)?),
+            ("thesis_dao", "application_submitted") => {
+                DaoEvent::ApplicationSubmitted(serde_json::from_value(data)?)
+            }
+            ("thesis_dao", "application_decided") => DaoEvent::ApplicationDecided(serde_json::from_value(data)?),
+            ("thesis_dao", "candidate_nominated") => DaoEvent::CandidateNominated(serde_json::from_value(data)?),
+            ("thesis_dao", "election_voted") => DaoEvent::ElectionVoted(serde_json::from_value(data)?),
+            ("thesis_dao", "election_finalized") => DaoEvent::ElectionFinalized(serde_json::from_value(data)?),
+            ("thesis_dao", "stream_created") => DaoEvent::StreamCreated(serde_json::from_value(data)?),
+            ("thesis_dao", "stream_withdrawn") => DaoEvent::StreamWithdrawn(serde_json::from_value(data)?),
+            ("thesis_dao", "stream_cancelled") => DaoEvent::StreamCancelled(serde_json::from_value(data)?),
+            ("thesis_dao", "grant_created") => DaoEvent::GrantCreated(serde_json::from_value(data)?),
+            ("thesis_dao", "milestone_submitted") => DaoEvent::MilestoneSubmitted(serde_json::from_value(data)?),
+            ("thesis_dao", "milestone_released") => DaoEvent::MilestoneReleased(serde_json::from_value(data)?),
+            ("thesis_dao", "grant_cancelled") => DaoEvent::GrantCancelled(serde_json::from_value(data)?),
+            ("thesis_dao", "bounty_created") => DaoEvent::BountyCreated(serde_json::from_value(data)?),
+            ("thesis_dao", "bounty_claimed") => DaoEvent::BountyClaimed(serde_json::from_value(data)?),
+            ("thesis_dao", "bounty_submitted") => DaoEvent::BountySubmitted(serde_json::from_value(data)?),
+            ("thesis_dao", "bounty_claim_reviewed") => {
+                DaoEvent::BountyClaimReviewed(serde_json::from_value(data)?)
+            }
+            ("thesis_dao", "bounty_cancelled") => DaoEvent::BountyCancelled(serde_json::from_value(data)?),
+            ("thesis_dao", "treasury_deposit") => DaoEvent::TreasuryDeposit(serde_json::from_value(data)?),
+            ("thesis_dao", "upgrade_code_staged") => DaoEvent::UpgradeCodeStaged(serde_json::from_value(data)?),
+            ("thesis_dao", "upgrade_status_changed") => {
+                DaoEvent::UpgradeStatusChanged(serde_json::from_value(data)?)
+            }
+            _ => DaoEvent::Other,
+        })
+    }
+}
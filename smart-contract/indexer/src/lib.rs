@@ -0,0 +1,33 @@
+//! Indexer für den THESIS DAO Contract: liest `EVENT_JSON:` Logs aus Transaktions-Outcomes
+//! und baut daraus Proposals, Stimmen, Guthaben, Rollen, Dividenden-Runden, Mitgliedsanträge, Wahlen,
+//! Streams, Grants, Bounties, Treasury-Eingänge und Upgrades in SQLite auf.
+
+pub mod event;
+pub mod source;
+pub mod store;
+
+use anyhow::Result;
+
+use crate::event::parse_log;
+use crate::source::TxOutcome;
+use crate::store::Store;
+
+/// Verarbeitet alle Event-Logs eines Outcomes. Mit `contract_id` werden nur Logs
+/// dieses Contracts berücksichtigt. Gibt die Anzahl neuer Events zurück.
+pub fn index_outcome(store: &mut Store, outcome: &TxOutcome, contract_id: Option<&str>) -> Result<usize> {
+    let mut count = 0;
+    for receipt in &outcome.receipts_outcome {
+        if contract_id.is_some_and(|contract_id| receipt.outcome.executor_id != contract_id) {
+            continue;
+        }
+        for (log_index, log) in receipt.outcome.logs.iter().enumerate() {
+            let Some(raw) = parse_log(log) else {
+                continue;
+            };
+            if store.apply(&receipt.id, log_index, receipt.block_hash.as_deref(), &raw)? {
+                count += 1;
+            }
+        }
+    }
+    Ok(count)
+}
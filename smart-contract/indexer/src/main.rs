@@ -0,0 +1,84 @@
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use thesis_dao_indexer::source::{read_fixture, Rpc};
+use thesis_dao_indexer::store::Store;
+use thesis_dao_indexer::index_outcome;
+
+#[derive(Parser)]
+#[command(about = "Rebuilds THESIS DAO state from EVENT_JSON logs into SQLite")]
+struct Cli {
+    /// SQLite-Datenbank (wird bei Bedarf angelegt)
+    #[arg(long, default_value = "thesis-dao.sqlite")]
+    db: String,
+    /// Nur Logs dieses Contracts verarbeiten
+    #[arg(long)]
+    contract: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Aufgezeichnete Outcomes (JSON) offline einlesen
+    Fixtures { files: Vec<String> },
+    /// Blöcke über JSON-RPC lesen (z.B. Sandbox unter http://localhost:3030)
+    Rpc {
+        #[arg(long, default_value = "http://localhost:3030")]
+        url: String,
+        /// Start-Höhe; Standard ist die zuletzt verarbeitete Höhe + 1
+        #[arg(long)]
+        from_height: Option<u64>,
+        /// Nach Erreichen des finalen Blocks weiter auf neue Blöcke warten
+        #[arg(long)]
+        follow: bool,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db)?;
+
+    match cli.command {
+        Command::Fixtures { files } => {
+            for file in files {
+                let mut count = 0;
+                for outcome in read_fixture(&file)? {
+                    count += index_outcome(&mut store, &outcome, cli.contract.as_deref())?;
+                }
+                println!("{}: {} new events", file, count);
+            }
+        }
+        Command::Rpc { url, from_height, follow } => {
+            let contract_id = cli
+                .contract
+                .ok_or_else(|| anyhow::anyhow!("--contract is required in RPC mode"))?;
+            let rpc = Rpc::new(&url);
+            let mut height = match from_height {
+                Some(height) => height,
+                None => store.last_height()?.map_or(0, |height| height + 1),
+            };
+            loop {
+                let final_height = rpc.final_height()?;
+                while height <= final_height {
+                    for outcome in rpc.contract_outcomes_at(height, &contract_id)? {
+                        let count = index_outcome(&mut store, &outcome, Some(&contract_id))?;
+                        if count > 0 {
+                            println!("#{}: {} new events", height, count);
+                        }
+                    }
+                    store.set_last_height(height)?;
+                    height += 1;
+                }
+                if !follow {
+                    break;
+                }
+                sleep(Duration::from_secs(1));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Ausschnitt eines `tx`-RPC-Resultats (FinalExecutionOutcome), wie er auch als Fixture gespeichert wird
+#[derive(Deserialize, Debug, Clone)]
+pub struct TxOutcome {
+    pub receipts_outcome: Vec<ReceiptOutcome>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ReceiptOutcome {
+    pub id: String,
+    pub block_hash: Option<String>,
+    pub outcome: Outcome,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Outcome {
+    pub logs: Vec<String>,
+    pub executor_id: String,
+}
+
+/// Liest eine Fixture-Datei: ein einzelnes Outcome oder eine Liste davon
+pub fn read_fixture(path: &str) -> Result<Vec<TxOutcome>> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path))?;
+    let value: Value = serde_json::from_str(&content)?;
+    Ok(match value {
+        Value::Array(_) => serde_json::from_value(value)?,
+        _ => vec![serde_json::from_value(value)?],
+    })
+}
+
+/// Minimaler JSON-RPC Client (Sandbox, Testnet, Mainnet oder eigener Node)
+pub struct Rpc {
+    url: String,
+}
+
+impl Rpc {
+    pub fn new(url: &str) -> Self {
+        Self { url: url.to_string() }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": "indexer", "method": method, "params": params });
+        let mut response: Value = ureq::post(&self.url).send_json(&body)?.body_mut().read_json()?;
+        if let Some(error) = response.get("error") {
+            return Err(anyhow!("RPC {} failed: {}", method, error));
+        }
+        Ok(response["result"].take())
+    }
+
+    pub fn final_height(&self) -> Result<u64> {
+        let block = self.call("block", json!({ "finality": "final" }))?;
+        block["header"]["height"]
+            .as_u64()
+            .ok_or_else(|| anyhow!("Missing block height"))
+    }
+
+    /// Outcomes aller Transaktionen eines Blocks, die an `contract_id` gerichtet sind.
+    /// Übersprungene Höhen (kein Block) liefern eine leere Liste.
+    pub fn contract_outcomes_at(&self, height: u64, contract_id: &str) -> Result<Vec<TxOutcome>> {
+        let block = match self.call("block", json!({ "block_id": height })) {
+            Ok(block) => block,
+            Err(error) if error.to_string().contains("UNKNOWN_BLOCK") => return Ok(vec![]),
+            Err(error) => return Err(error),
+        };
+        let mut outcomes = vec![];
+        for chunk in block["chunks"].as_array().cloned().unwrap_or_default() {
+            let chunk = self.call("chunk", json!({ "chunk_id": chunk["chunk_hash"] }))?;
+            for transaction in chunk["transactions"].as_array().cloned().unwrap_or_default() {
+                if transaction["receiver_id"] != contract_id {
+                    continue;
+                }
+                let outcome = self.call(
+                    "tx",
+                    json!({
+                        "tx_hash": transaction["hash"],
+                        "sender_account_id": transaction["signer_id"],
+                        "wait_until": "FINAL",
+                    }),
+                )?;
+                outcomes.push(serde_json::from_value(outcome)?);
+            }
+        }
+        Ok(outcomes)
+    }
+}
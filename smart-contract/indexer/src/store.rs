@@ -0,0 +1,633 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::event::{DaoEvent, RawEvent};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    receipt_id TEXT NOT NULL,
+    log_index INTEGER NOT NULL,
+    block_hash TEXT,
+    standard TEXT NOT NULL,
+    version TEXT NOT NULL,
+    event TEXT NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (receipt_id, log_index)
+);
+CREATE TABLE IF NOT EXISTS balances (
+    account_id TEXT PRIMARY KEY,
+    balance TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS proposals (
+    id INTEGER PRIMARY KEY,
+    proposer TEXT NOT NULL,
+    title TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    deadline INTEGER,
+    status TEXT NOT NULL,
+    votes_for TEXT NOT NULL DEFAULT '0',
+    votes_against TEXT NOT NULL DEFAULT '0',
+    executed INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS votes (
+    proposal_id INTEGER NOT NULL,
+    voter TEXT NOT NULL,
+    support INTEGER NOT NULL,
+    weight TEXT NOT NULL,
+    PRIMARY KEY (proposal_id, voter)
+);
+CREATE TABLE IF NOT EXISTS roles (
+    account_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    expires_at INTEGER,
+    PRIMARY KEY (account_id, role)
+);
+CREATE TABLE IF NOT EXISTS team (
+    account_id TEXT PRIMARY KEY
+);
+CREATE TABLE IF NOT EXISTS cursor (
+    name TEXT PRIMARY KEY,
+    value INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS dividend_rounds (
+    receipt_id TEXT PRIMARY KEY,
+    total_amount TEXT NOT NULL,
+    total_supply TEXT NOT NULL,
+    recipients INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS actions (
+    id INTEGER PRIMARY KEY,
+    action TEXT NOT NULL,
+    approvals INTEGER NOT NULL,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS applications (
+    id INTEGER PRIMARY KEY,
+    applicant TEXT NOT NULL,
+    requested_role TEXT NOT NULL,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS election_candidates (
+    term_id INTEGER NOT NULL,
+    candidate TEXT NOT NULL,
+    votes TEXT NOT NULL DEFAULT '0',
+    elected INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (term_id, candidate)
+);
+CREATE TABLE IF NOT EXISTS election_ballots (
+    term_id INTEGER NOT NULL,
+    voter TEXT NOT NULL,
+    candidates TEXT NOT NULL,
+    weight TEXT NOT NULL,
+    PRIMARY KEY (term_id, voter)
+);
+CREATE TABLE IF NOT EXISTS council (
+    account_id TEXT PRIMARY KEY
+);
+CREATE TABLE IF NOT EXISTS streams (
+    id INTEGER PRIMARY KEY,
+    recipient TEXT NOT NULL,
+    asset TEXT NOT NULL,
+    rate_per_sec TEXT NOT NULL,
+    start INTEGER NOT NULL,
+    end INTEGER NOT NULL,
+    cliff INTEGER NOT NULL,
+    withdrawn TEXT NOT NULL DEFAULT '0',
+    cancelled_at INTEGER
+);
+CREATE TABLE IF NOT EXISTS grants (
+    id INTEGER PRIMARY KEY,
+    grantee TEXT NOT NULL,
+    asset TEXT NOT NULL,
+    cancelled INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS milestones (
+    grant_id INTEGER NOT NULL,
+    milestone_index INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    status TEXT NOT NULL,
+    evidence_link TEXT,
+    PRIMARY KEY (grant_id, milestone_index)
+);
+CREATE TABLE IF NOT EXISTS bounties (
+    id INTEGER PRIMARY KEY,
+    description TEXT NOT NULL,
+    asset TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    max_claims INTEGER NOT NULL,
+    deadline INTEGER NOT NULL,
+    cancelled INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS bounty_claims (
+    bounty_id INTEGER NOT NULL,
+    account_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    expires_at INTEGER NOT NULL,
+    proof TEXT,
+    PRIMARY KEY (bounty_id, account_id)
+);
+CREATE TABLE IF NOT EXISTS treasury_deposits (
+    receipt_id TEXT NOT NULL,
+    log_index INTEGER NOT NULL,
+    asset TEXT NOT NULL,
+    sender TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    PRIMARY KEY (receipt_id, log_index)
+);
+CREATE TABLE IF NOT EXISTS upgrades (
+    code_hash TEXT PRIMARY KEY,
+    staged_by TEXT,
+    proposal_id INTEGER,
+    status TEXT NOT NULL
+);
+";
+
+/// SQLite-Abbild des DAO-Zustands, aufgebaut ausschliesslich aus Event-Logs
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &str) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Verarbeitet ein Event genau einmal; bereits bekannte (receipt_id, log_index) werden übersprungen.
+    /// Gibt `true` zurück, falls das Event neu war.
+    pub fn apply(&mut self, receipt_id: &str, log_index: usize, block_hash: Option<&str>, raw: &RawEvent) -> Result<bool> {
+        let tx = self.conn.transaction()?;
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO events (receipt_id, log_index, block_hash, standard, version, event, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                receipt_id,
+                log_index as i64,
+                block_hash,
+                raw.standard,
+                raw.version,
+                raw.event,
+                raw.data.to_string()
+            ],
+        )?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        match DaoEvent::from_raw(raw)? {
+            DaoEvent::FtMint(mints) => {
+                for mint in mints {
+                    change_balance(&tx, &mint.owner_id, mint.amount, true)?;
+                }
+            }
+            DaoEvent::FtTransfer(transfers) => {
+                for transfer in transfers {
+                    change_balance(&tx, &transfer.old_owner_id, transfer.amount, false)?;
+                    change_balance(&tx, &transfer.new_owner_id, transfer.amount, true)?;
+                }
+            }
+            DaoEvent::ProposalCreated(proposals) => {
+                for proposal in proposals {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO proposals (id, proposer, title, kind, deadline, status)
+                         VALUES (?1, ?2, ?3, ?4, ?5, 'Open')",
+                        params![
+                            proposal.proposal_id as i64,
+                            proposal.proposer,
+                            proposal.title,
+                            proposal.kind.to_string(),
+                            proposal.deadline.map(|d| d as i64)
+                        ],
+                    )?;
+                }
+            }
+            DaoEvent::ProposalVoted(votes) => {
+                for vote in votes {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO votes (proposal_id, voter, support, weight) VALUES (?1, ?2, ?3, ?4)",
+                        params![vote.proposal_id as i64, vote.voter, vote.support, vote.weight.to_string()],
+                    )?;
+                }
+            }
+            DaoEvent::ProposalFinalized(results) => {
+                for result in results {
+                    tx.execute(
+                        "UPDATE proposals SET status = ?2, votes_for = ?3, votes_against = ?4 WHERE id = ?1",
+                        params![
+                            result.proposal_id as i64,
+                            result.status,
+                            result.votes_for.to_string(),
+                            result.votes_against.to_string()
+                        ],
+                    )?;
+                }
+            }
+            DaoEvent::ProposalExecuted(executions) => {
+                for execution in executions {
+                    tx.execute(
+                        "UPDATE proposals SET executed = 1 WHERE id = ?1",
+                        params![execution.proposal_id as i64],
+                    )?;
+                }
+            }
+            DaoEvent::ProposalPayoutResolved(payouts) => {
+                for payout in payouts {
+                    tx.execute(
+                        "UPDATE proposals SET status = ?2 WHERE id = ?1",
+                        params![payout.proposal_id as i64, payout.status],
+                    )?;
+                }
+            }
+            DaoEvent::RoleChanged(changes) => {
+                for change in changes {
+                    if !change.granted {
+                        tx.execute(
+                            "DELETE FROM roles WHERE account_id = ?1 AND role = ?2",
+                            params![change.account_id, change.role],
+                        )?;
+                    } else {
+                        // Visitor ersetzt alle anderen Rollen (wie im Contract)
+                        if change.role == "visitor" {
+                            tx.execute("DELETE FROM roles WHERE account_id = ?1", params![change.account_id])?;
+                        }
+                        tx.execute(
+                            "INSERT OR REPLACE INTO roles (account_id, role, expires_at) VALUES (?1, ?2, ?3)",
+                            params![change.account_id, change.role, change.expires_at.map(|e| e as i64)],
+                        )?;
+                    }
+                }
+            }
+            DaoEvent::TeamChanged(changes) => {
+                for change in changes {
+                    if change.added {
+                        tx.execute("INSERT OR IGNORE INTO team (account_id) VALUES (?1)", params![change.account_id])?;
+                    } else {
+                        tx.execute("DELETE FROM team WHERE account_id = ?1", params![change.account_id])?;
+                    }
+                }
+            }
+            DaoEvent::DividendsDistributed(rounds) => {
+                for round in rounds {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO dividend_rounds (receipt_id, total_amount, total_supply, recipients)
+                         VALUES (?1, ?2, ?3, ?4)",
+                        params![
+                            receipt_id,
+                            round.total_amount.to_string(),
+                            round.total_supply.to_string(),
+                            round.recipients
+                        ],
+                    )?;
+                }
+            }
+            DaoEvent::ActionApproved(approvals) => {
+                for approval in approvals {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO actions (id, action, approvals, status) VALUES (?1, ?2, ?3, ?4)",
+                        params![
+                            approval.action_id as i64,
+                            approval.action.to_string(),
+                            approval.approvals,
+                            if approval.executed { "Executed" } else { "Pending" }
+                        ],
+                    )?;
+                }
+            }
+            DaoEvent::ActionCancelled(cancellations) => {
+                for cancellation in cancellations {
+                    tx.execute(
+                        "UPDATE actions SET status = 'Cancelled' WHERE id = ?1",
+                        params![cancellation.action_id as i64],
+                    )?;
+                }
+            }
+            DaoEvent::ApplicationSubmitted(applications) => {
+                for application in applications {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO applications (id, applicant, requested_role, status)
+                         VALUES (?1, ?2, ?3, 'Pending')",
+                        params![application.application_id as i64, application.applicant, application.requested_role],
+                    )?;
+                }
+            }
+            DaoEvent::ApplicationDecided(decisions) => {
+                for decision in decisions {
+                    tx.execute(
+                        "UPDATE applications SET status = ?2 WHERE id = ?1",
+                        params![decision.application_id as i64, decision.status],
+                    )?;
+                }
+            }
+            DaoEvent::CandidateNominated(nominations) => {
+                for nomination in nominations {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO election_candidates (term_id, candidate) VALUES (?1, ?2)",
+                        params![nomination.term_id as i64, nomination.candidate],
+                    )?;
+                }
+            }
+            DaoEvent::ElectionVoted(ballots) => {
+                for ballot in ballots {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO election_ballots (term_id, voter, candidates, weight)
+                         VALUES (?1, ?2, ?3, ?4)",
+                        params![
+                            ballot.term_id as i64,
+                            ballot.voter,
+                            serde_json::to_string(&ballot.candidates)?,
+                            ballot.weight.to_string()
+                        ],
+                    )?;
+                    for candidate in &ballot.candidates {
+                        add_amount(
+                            &tx,
+                            "election_candidates",
+                            "votes",
+                            "term_id = ?1 AND candidate = ?2",
+                            params![ballot.term_id as i64, candidate],
+                            ballot.weight,
+                        )?;
+                    }
+                }
+            }
+            DaoEvent::ElectionFinalized(results) => {
+                for result in results {
+                    for winner in &result.winners {
+                        tx.execute(
+                            "UPDATE election_candidates SET elected = 1 WHERE term_id = ?1 AND candidate = ?2",
+                            params![result.term_id as i64, winner],
+                        )?;
+                    }
+                    tx.execute("DELETE FROM council", [])?;
+                    for member in &result.council {
+                        tx.execute("INSERT INTO council (account_id) VALUES (?1)", params![member])?;
+                    }
+                }
+            }
+            DaoEvent::StreamCreated(streams) => {
+                for stream in streams {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO streams (id, recipient, asset, rate_per_sec, start, end, cliff)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![
+                            stream.id as i64,
+                            stream.recipient,
+                            stream.asset.to_string(),
+                            stream.rate_per_sec.to_string(),
+                            stream.start as i64,
+                            stream.end as i64,
+                            stream.cliff as i64
+                        ],
+                    )?;
+                }
+            }
+            DaoEvent::StreamWithdrawn(withdrawals) => {
+                for withdrawal in withdrawals {
+                    add_amount(
+                        &tx,
+                        "streams",
+                        "withdrawn",
+                        "id = ?1",
+                        params![withdrawal.stream_id as i64],
+                        withdrawal.amount,
+                    )?;
+                }
+            }
+            DaoEvent::StreamCancelled(cancellations) => {
+                for cancellation in cancellations {
+                    tx.execute(
+                        "UPDATE streams SET cancelled_at = ?2 WHERE id = ?1",
+                        params![cancellation.stream_id as i64, cancellation.cancelled_at as i64],
+                    )?;
+                }
+            }
+            DaoEvent::GrantCreated(grants) => {
+                for grant in grants {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO grants (id, grantee, asset) VALUES (?1, ?2, ?3)",
+                        params![grant.id as i64, grant.grantee, grant.asset.to_string()],
+                    )?;
+                    for (index, milestone) in grant.milestones.iter().enumerate() {
+                        tx.execute(
+                            "INSERT OR REPLACE INTO milestones (grant_id, milestone_index, description, amount, status)
+                             VALUES (?1, ?2, ?3, ?4, 'Pending')",
+                            params![grant.id as i64, index as i64, milestone.description, milestone.amount.to_string()],
+                        )?;
+                    }
+                }
+            }
+            DaoEvent::MilestoneSubmitted(submissions) => {
+                for submission in submissions {
+                    tx.execute(
+                        "UPDATE milestones SET status = 'Submitted', evidence_link = ?3
+                         WHERE grant_id = ?1 AND milestone_index = ?2",
+                        params![submission.grant_id as i64, submission.milestone_index, submission.evidence_link],
+                    )?;
+                }
+            }
+            DaoEvent::MilestoneReleased(releases) => {
+                for release in releases {
+                    tx.execute(
+                        "UPDATE milestones SET status = 'Released' WHERE grant_id = ?1 AND milestone_index = ?2",
+                        params![release.grant_id as i64, release.milestone_index],
+                    )?;
+                }
+            }
+            DaoEvent::GrantCancelled(cancellations) => {
+                for cancellation in cancellations {
+                    tx.execute(
+                        "UPDATE grants SET cancelled = 1 WHERE id = ?1",
+                        params![cancellation.grant_id as i64],
+                    )?;
+                }
+            }
+            DaoEvent::BountyCreated(bounties) => {
+                for bounty in bounties {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO bounties (id, description, asset, amount, max_claims, deadline)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            bounty.id as i64,
+                            bounty.description,
+                            bounty.asset.to_string(),
+                            bounty.amount.to_string(),
+                            bounty.max_claims,
+                            bounty.deadline as i64
+                        ],
+                    )?;
+                }
+            }
+            DaoEvent::BountyClaimed(claims) => {
+                for claim in claims {
+                    // Ein abgelaufener Claim desselben Accounts wird ersetzt (wie im Contract)
+                    tx.execute(
+                        "INSERT OR REPLACE INTO bounty_claims (bounty_id, account_id, status, expires_at)
+                         VALUES (?1, ?2, 'Claimed', ?3)",
+                        params![claim.bounty_id as i64, claim.account_id, claim.expires_at as i64],
+                    )?;
+                }
+            }
+            DaoEvent::BountySubmitted(submissions) => {
+                for submission in submissions {
+                    tx.execute(
+                        "UPDATE bounty_claims SET status = 'Submitted', proof = ?3 WHERE bounty_id = ?1 AND account_id = ?2",
+                        params![submission.bounty_id as i64, submission.account_id, submission.proof],
+                    )?;
+                }
+            }
+            DaoEvent::BountyClaimReviewed(reviews) => {
+                for review in reviews {
+                    if review.approved {
+                        tx.execute(
+                            "UPDATE bounty_claims SET status = 'Approved' WHERE bounty_id = ?1 AND account_id = ?2",
+                            params![review.bounty_id as i64, review.claimant],
+                        )?;
+                    } else {
+                        // Abgelehnte Einreichungen geben den Platz frei (wie im Contract)
+                        tx.execute(
+                            "DELETE FROM bounty_claims WHERE bounty_id = ?1 AND account_id = ?2",
+                            params![review.bounty_id as i64, review.claimant],
+                        )?;
+                    }
+                }
+            }
+            DaoEvent::BountyCancelled(cancellations) => {
+                for cancellation in cancellations {
+                    tx.execute(
+                        "UPDATE bounties SET cancelled = 1 WHERE id = ?1",
+                        params![cancellation.bounty_id as i64],
+                    )?;
+                }
+            }
+            DaoEvent::TreasuryDeposit(deposits) => {
+                for deposit in deposits {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO treasury_deposits (receipt_id, log_index, asset, sender, amount)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![
+                            receipt_id,
+                            log_index as i64,
+                            deposit.asset.to_string(),
+                            deposit.sender,
+                            deposit.amount.to_string()
+                        ],
+                    )?;
+                }
+            }
+            DaoEvent::UpgradeCodeStaged(stagings) => {
+                for staging in stagings {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO upgrades (code_hash, staged_by, status) VALUES (?1, ?2, 'Staged')",
+                        params![staging.code_hash, staging.staged_by],
+                    )?;
+                }
+            }
+            DaoEvent::UpgradeStatusChanged(changes) => {
+                for change in changes {
+                    tx.execute(
+                        "INSERT INTO upgrades (code_hash, proposal_id, status) VALUES (?1, ?2, ?3)
+                         ON CONFLICT(code_hash) DO UPDATE SET proposal_id = ?2, status = ?3",
+                        params![change.code_hash, change.proposal_id as i64, change.status],
+                    )?;
+                }
+            }
+            DaoEvent::Other => {}
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Zuletzt vollständig verarbeitete Blockhöhe (RPC-Modus)
+    pub fn last_height(&self) -> Result<Option<u64>> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM cursor WHERE name = 'last_height'", [], |row| row.get::<_, i64>(0))
+            .optional()?
+            .map(|height| height as u64))
+    }
+
+    pub fn set_last_height(&self, height: u64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO cursor (name, value) VALUES ('last_height', ?1)",
+            params![height as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn balance_of(&self, account_id: &str) -> Result<u128> {
+        let balance: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT balance FROM balances WHERE account_id = ?1",
+                params![account_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(balance.map_or(Ok(0), |b| b.parse())?)
+    }
+}
+
+/// Addiert einen Betrag auf eine Dezimal-String-Spalte einer bestehenden Zeile
+fn add_amount(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    filter: &str,
+    filter_params: &[&dyn rusqlite::ToSql],
+    amount: u128,
+) -> Result<()> {
+    let current: Option<String> = conn
+        .query_row(&format!("SELECT {column} FROM {table} WHERE {filter}"), filter_params, |row| row.get(0))
+        .optional()?;
+    let Some(current) = current else {
+        return Ok(());
+    };
+    let updated = current
+        .parse::<u128>()?
+        .checked_add(amount)
+        .ok_or_else(|| anyhow::anyhow!("{}.{} out of range", table, column))?;
+    let mut update_params = filter_params.to_vec();
+    let updated = updated.to_string();
+    update_params.push(&updated);
+    conn.execute(
+        &format!("UPDATE {table} SET {column} = ?{} WHERE {filter}", update_params.len()),
+        update_params.as_slice(),
+    )?;
+    Ok(())
+}
+
+/// Beträge werden als Dezimal-String gespeichert, da SQLite keine u128 kennt
+fn change_balance(conn: &Connection, account_id: &str, amount: u128, credit: bool) -> Result<()> {
+    let current: Option<String> = conn
+        .query_row(
+            "SELECT balance FROM balances WHERE account_id = ?1",
+            params![account_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let current: u128 = current.map_or(Ok(0), |b| b.parse())?;
+    let updated = if credit {
+        current.checked_add(amount)
+    } else {
+        current.checked_sub(amount)
+    }
+    .ok_or_else(|| anyhow::anyhow!("Balance of {} out of range", account_id))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO balances (account_id, balance) VALUES (?1, ?2)",
+        params![account_id, updated.to_string()],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use thesis_dao_indexer::event::parse_log;
+use thesis_dao_indexer::source::read_fixture;
+use thesis_dao_indexer::store::Store;
+use thesis_dao_indexer::index_outcome;
+
+#[test]
+fn test_rebuild_state_from_fixture() -> anyhow::Result<()> {
+    let mut store = Store::open_in_memory()?;
+    let outcomes = read_fixture(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/governance_flow.json"))?;
+    let mut count = 0;
+    for outcome in &outcomes {
+        count += index_outcome(&mut store, outcome, Some("dao.test.near"))?;
+    }
+    assert_eq!(count, 10);
+
+    assert_eq!(store.balance_of("treasury.test.near")?, 3_000 * 10u128.pow(24) - 250);
+    assert_eq!(store.balance_of("alice.test.near")?, 750);
+    assert_eq!(store.balance_of("y.test.near")?, 0);
+
+    let (status, executed): (String, bool) = store.connection().query_row(
+        "SELECT status, executed FROM proposals WHERE id = 0",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    assert_eq!(status, "Accepted");
+    assert!(executed);
+
+    // Erneutes Einlesen verändert nichts
+    for outcome in &outcomes {
+        assert_eq!(index_outcome(&mut store, outcome, Some("dao.test.near"))?, 0);
+    }
+    assert_eq!(store.balance_of("alice.test.near")?, 750);
+    Ok(())
+}
+
+#[test]
+fn test_payout_result_sets_final_status() -> anyhow::Result<()> {
+    let mut store = Store::open_in_memory()?;
+    let logs = [
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"proposal_created","data":[{"proposal_id":1,"proposer":"alice.test.near","title":"Pay","kind":{"NearTransfer":{"receiver_id":"bob.test.near","amount":"5"}},"deadline":null}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"proposal_finalized","data":[{"proposal_id":1,"status":"Accepted","votes_for":"10","votes_against":"0"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"proposal_payout_resolved","data":[{"proposal_id":1,"asset":"Near","amount":"5","status":"Failed"}]}"#,
+    ];
+    for (index, log) in logs.iter().enumerate() {
+        let raw = parse_log(log).expect("event log");
+        assert!(store.apply("payout-receipt", index, None, &raw)?);
+    }
+
+    let (status, executed): (String, bool) = store.connection().query_row(
+        "SELECT status, executed FROM proposals WHERE id = 1",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    assert_eq!(status, "Failed");
+    assert!(!executed);
+    Ok(())
+}
+
+#[test]
+fn test_module_events_are_materialized() -> anyhow::Result<()> {
+    let mut store = Store::open_in_memory()?;
+    let logs = [
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"treasury_deposit","data":[{"asset":"Near","sender":"donor.test.near","amount":"10000"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"application_submitted","data":[{"application_id":0,"applicant":"carol.test.near","requested_role":"community"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"application_decided","data":[{"application_id":0,"applicant":"carol.test.near","status":"Approved","decided_by":"owner.test.near"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"candidate_nominated","data":[{"term_id":0,"candidate":"carol.test.near"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"candidate_nominated","data":[{"term_id":0,"candidate":"dave.test.near"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"election_voted","data":[{"term_id":0,"voter":"alice.test.near","candidates":["carol.test.near"],"weight":"750"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"election_voted","data":[{"term_id":0,"voter":"bob.test.near","candidates":["carol.test.near","dave.test.near"],"weight":"250"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"election_finalized","data":[{"term_id":0,"winners":["carol.test.near"],"council":["carol.test.near"]}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"stream_created","data":[{"id":0,"recipient":"bob.test.near","asset":"Near","rate_per_sec":"5","start":1700000000,"end":1700000100,"cliff":1700000000,"withdrawn":"0","cancelled_at":null}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"stream_withdrawn","data":[{"stream_id":0,"recipient":"bob.test.near","amount":"150"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"stream_withdrawn","data":[{"stream_id":0,"recipient":"bob.test.near","amount":"50"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"stream_cancelled","data":[{"stream_id":0,"cancelled_at":1700000040,"refunded":"300"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"grant_created","data":[{"id":0,"grantee":"bob.test.near","asset":"Near","milestones":[{"description":"a","amount":"7","status":"Pending","evidence_link":null,"evidence_hash":null,"submitted_at":null,"released_at":null},{"description":"b","amount":"9","status":"Pending","evidence_link":null,"evidence_hash":null,"submitted_at":null,"released_at":null}],"created_at":1700000000,"cancelled":false}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"milestone_submitted","data":[{"grant_id":0,"milestone_index":0,"evidence_link":"https://example.org","evidence_hash":"h"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"milestone_released","data":[{"grant_id":0,"milestone_index":0,"grantee":"bob.test.near","amount":"7"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"grant_cancelled","data":[{"grant_id":0,"refunded":"9"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"bounty_created","data":[{"id":0,"description":"d","asset":"Near","amount":"3","max_claims":2,"deadline":1700001000,"claim_period_sec":100,"claims":[],"cancelled":false}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"bounty_claimed","data":[{"bounty_id":0,"account_id":"bob.test.near","expires_at":1700000100}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"bounty_claimed","data":[{"bounty_id":0,"account_id":"carol.test.near","expires_at":1700000100}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"bounty_submitted","data":[{"bounty_id":0,"account_id":"bob.test.near","proof":"pr/1"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"bounty_submitted","data":[{"bounty_id":0,"account_id":"carol.test.near","proof":"pr/2"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"bounty_claim_reviewed","data":[{"bounty_id":0,"claimant":"bob.test.near","approved":true,"amount":"3"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"bounty_claim_reviewed","data":[{"bounty_id":0,"claimant":"carol.test.near","approved":false}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"bounty_cancelled","data":[{"bounty_id":0,"refunded":"3"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"action_approved","data":[{"action_id":4,"action":{"DistributeDividends":{"amount":"10"}},"signer":"owner.test.near","approvals":1,"executed":false}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"action_cancelled","data":[{"action_id":4,"action":{"DistributeDividends":{"amount":"10"}},"actor":"owner.test.near"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"upgrade_code_staged","data":[{"code_hash":"4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi","staged_by":"owner.test.near","storage_cost":"100"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"upgrade_status_changed","data":[{"proposal_id":3,"code_hash":"4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi","status":"Scheduled"}]}"#,
+        r#"EVENT_JSON:{"standard":"thesis_dao","version":"1.0.0","event":"upgrade_status_changed","data":[{"proposal_id":3,"code_hash":"4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi","status":"Deployed"}]}"#,
+    ];
+    for (index, log) in logs.iter().enumerate() {
+        let raw = parse_log(log).expect("event log");
+        assert!(store.apply("module-receipt", index, None, &raw)?);
+    }
+    let conn = store.connection();
+    let text = |sql: &str| -> rusqlite::Result<String> { conn.query_row(sql, [], |row| row.get(0)) };
+    let number = |sql: &str| -> rusqlite::Result<i64> { conn.query_row(sql, [], |row| row.get(0)) };
+
+    assert_eq!(text("SELECT amount FROM treasury_deposits")?, "10000");
+    assert_eq!(text("SELECT status FROM applications WHERE id = 0")?, "Approved");
+    assert_eq!(text("SELECT votes FROM election_candidates WHERE candidate = 'carol.test.near'")?, "1000");
+    assert_eq!(text("SELECT votes FROM election_candidates WHERE candidate = 'dave.test.near'")?, "250");
+    assert_eq!(number("SELECT elected FROM election_candidates WHERE candidate = 'carol.test.near'")?, 1);
+    assert_eq!(text("SELECT account_id FROM council")?, "carol.test.near");
+    assert_eq!(text("SELECT withdrawn FROM streams WHERE id = 0")?, "200");
+    assert_eq!(number("SELECT cancelled_at FROM streams WHERE id = 0")?, 1_700_000_040);
+    assert_eq!(text("SELECT status FROM milestones WHERE grant_id = 0 AND milestone_index = 0")?, "Released");
+    assert_eq!(text("SELECT status FROM milestones WHERE grant_id = 0 AND milestone_index = 1")?, "Pending");
+    assert_eq!(number("SELECT cancelled FROM grants WHERE id = 0")?, 1);
+    assert_eq!(text("SELECT status FROM bounty_claims WHERE account_id = 'bob.test.near'")?, "Approved");
+    assert_eq!(number("SELECT COUNT(*) FROM bounty_claims WHERE account_id = 'carol.test.near'")?, 0);
+    assert_eq!(number("SELECT cancelled FROM bounties WHERE id = 0")?, 1);
+    assert_eq!(text("SELECT status FROM actions WHERE id = 4")?, "Cancelled");
+    assert_eq!(text("SELECT status FROM upgrades")?, "Deployed");
+    assert_eq!(text("SELECT staged_by FROM upgrades")?, "owner.test.near");
+    Ok(())
+}
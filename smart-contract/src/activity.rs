@@ -0,0 +1,159 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Anzahl Einträge im Ring-Puffer; ältere Einträge werden überschrieben
+pub const ACTIVITY_LOG_CAPACITY: u64 = 10_000;
+/// Maximale Anzahl Einträge im Index pro Account
+pub const ACCOUNT_ACTIVITY_CAPACITY: usize = 100;
+/// Speicherbedarf eines vollen Index für die längste Account-ID (40 Bytes Overhead, Schlüssel, Vec<u64>).
+/// Ist in storage_balance_bounds enthalten und wird damit vom Account selbst bezahlt.
+pub const ACCOUNT_ACTIVITY_STORAGE_BYTES: u64 = 40 + (1 + 4 + 64) + (4 + 8 * ACCOUNT_ACTIVITY_CAPACITY as u64);
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, PartialEq, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum ActivityKind {
+    Buy,
+    Transfer,
+    Proposal,
+    Vote,
+    RoleChange,
+    Dividend,
+}
+
+/// Kompakter Eintrag im On-Chain Aktivitätsprotokoll (Zeitstempel in ms).
+/// `reference` enthält je nach Art die Proposal-ID, die Rolle oder das Memo.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct ActivityRecord {
+    pub id: u64,
+    pub kind: ActivityKind,
+    pub account_id: AccountId,
+    pub counterparty: Option<AccountId>,
+    pub amount: Option<U128>,
+    pub reference: Option<String>,
+    pub timestamp: u64,
+}
+
+impl Contract {
+    pub(crate) fn internal_record_activity(
+        &mut self,
+        kind: ActivityKind,
+        account_id: &AccountId,
+        counterparty: Option<&AccountId>,
+        amount: Option<u128>,
+        reference: Option<String>,
+    ) {
+        let id = self.activity_count;
+        let record = ActivityRecord {
+            id,
+            kind,
+            account_id: account_id.clone(),
+            counterparty: counterparty.cloned(),
+            amount: amount.map(U128),
+            reference,
+            timestamp: env::block_timestamp_ms(),
+        };
+        self.activity_log.insert(&(id % ACTIVITY_LOG_CAPACITY), &record);
+        self.activity_count += 1;
+
+        self.internal_index_activity(account_id, id);
+        if let Some(counterparty) = counterparty {
+            if counterparty != account_id {
+                self.internal_index_activity(counterparty, id);
+            }
+        }
+    }
+
+    /// Nur registrierte Accounts haben den Index über ihren Storage-Deposit bezahlt
+    fn internal_index_activity(&mut self, account_id: &AccountId, id: u64) {
+        if self.accounts.get(account_id).is_none() {
+            return;
+        }
+        let mut ids = self.account_activity.get(account_id).unwrap_or_default();
+        ids.push(id);
+        if ids.len() > ACCOUNT_ACTIVITY_CAPACITY {
+            ids.remove(0);
+        }
+        self.account_activity.insert(account_id, &ids);
+    }
+
+    /// Eintrag mit der globalen ID `id`, falls er noch nicht überschrieben wurde
+    fn internal_activity(&self, id: u64) -> Option<ActivityRecord> {
+        if id >= self.activity_count || id < self.activity_count.saturating_sub(ACTIVITY_LOG_CAPACITY) {
+            return None;
+        }
+        self.activity_log.get(&(id % ACTIVITY_LOG_CAPACITY))
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Globales Aktivitätsprotokoll ab ID `from_index` (Standard: ältester noch vorhandener Eintrag)
+    pub fn get_activity(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<ActivityRecord> {
+        let oldest = self.activity_count.saturating_sub(ACTIVITY_LOG_CAPACITY);
+        let from_index = std::cmp::max(from_index.unwrap_or(oldest), oldest);
+        let limit = limit.unwrap_or(50);
+        (from_index..std::cmp::min(from_index.saturating_add(limit), self.activity_count))
+            .filter_map(|id| self.internal_activity(id))
+            .collect()
+    }
+
+    /// Aktivitäten eines Accounts (die letzten ACCOUNT_ACTIVITY_CAPACITY Einträge), paginiert
+    pub fn get_account_activity(
+        &self,
+        account_id: AccountId,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<ActivityRecord> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(50);
+        self.account_activity
+            .get(&account_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| self.internal_activity(id))
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Gesamtzahl der je protokollierten Aktivitäten
+    pub fn get_activity_count(&self) -> u64 {
+        self.activity_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StorageManagement;
+    use crate::test_utils::*;
+
+    #[test]
+    fn unregistered_actors_are_not_indexed() {
+        let mut contract = setup();
+        let bob = account("bob");
+        let owner_entries = contract.get_account_activity(owner(), None, None).len();
+        contract.internal_record_activity(ActivityKind::Vote, &bob, Some(&owner()), None, None);
+
+        assert!(contract.get_account_activity(bob, None, None).is_empty());
+        assert_eq!(contract.get_account_activity(owner(), None, None).len(), owner_entries + 1);
+        assert_eq!(contract.get_activity(None, None).last().unwrap().kind, ActivityKind::Vote);
+    }
+
+    #[test]
+    fn storage_deposit_covers_a_full_index() {
+        let mut contract = setup();
+        let longest: AccountId = "a".repeat(64).parse().unwrap();
+        let initial_storage_usage = env::storage_usage();
+        contract.account_activity.insert(&longest, &vec![u64::MAX; ACCOUNT_ACTIVITY_CAPACITY]);
+        assert_eq!(env::storage_usage() - initial_storage_usage, ACCOUNT_ACTIVITY_STORAGE_BYTES);
+
+        let bytes = contract.bytes_for_longest_account_id + ACCOUNT_ACTIVITY_STORAGE_BYTES;
+        assert_eq!(contract.storage_balance_bounds().min, env::storage_byte_cost().saturating_mul(bytes.into()));
+    }
+}
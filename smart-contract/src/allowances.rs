@@ -0,0 +1,230 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Per Proposal genehmigtes Ausgabenlimit eines Finance-Mitglieds (DAO-Tokens aus dem Treasury-Account).
+/// Zeitangaben in Sekunden; `allowed_recipients = None` erlaubt beliebige Empfänger.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Allowance {
+    pub account_id: AccountId,
+    pub per_tx_max: U128,
+    pub per_period_max: U128,
+    pub period_sec: u64,
+    pub period_start: u64,
+    pub spent: U128,
+    pub allowed_recipients: Option<Vec<AccountId>>,
+}
+
+/// Protokoll-Eintrag einer Ausgabe über finance_spend
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct FinanceSpendRecord {
+    pub spender: AccountId,
+    pub recipient: AccountId,
+    pub amount: U128,
+    pub memo: String,
+    pub timestamp: u64,
+}
+
+impl Contract {
+    pub(crate) fn internal_set_allowance(
+        &mut self,
+        account_id: AccountId,
+        per_tx_max: U128,
+        per_period_max: U128,
+        period_sec: u64,
+        allowed_recipients: Option<Vec<AccountId>>,
+    ) {
+        require!(
+            self.internal_has_role(&account_id, ROLE_FINANCE),
+            DaoError::FinanceRoleRequired.with_detail(&account_id)
+        );
+        require!(period_sec > 0, DaoError::InvalidAllowancePeriod.to_string());
+        require!(
+            per_tx_max.0 > 0 && per_tx_max.0 <= per_period_max.0,
+            DaoError::InvalidAllowanceLimits.to_string()
+        );
+        let allowance = Allowance {
+            account_id: account_id.clone(),
+            per_tx_max,
+            per_period_max,
+            period_sec,
+            period_start: env::block_timestamp_ms() / 1000,
+            spent: U128(0),
+            allowed_recipients,
+        };
+        self.allowances.insert(&account_id, &allowance);
+    }
+
+    pub(crate) fn internal_revoke_allowance(&mut self, account_id: AccountId) {
+        require!(
+            self.allowances.remove(&account_id).is_some(),
+            DaoError::AllowanceNotFound.with_detail(&account_id)
+        );
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Kleinausgabe ohne Abstimmung innerhalb des genehmigten Limits.
+    /// Die Tokens werden vom Treasury-Account an `recipient` übertragen.
+    #[payable]
+    pub fn finance_spend(&mut self, recipient: AccountId, amount: U128, memo: String) {
+        assert_one_yocto();
+        self.assert_not_paused(Subsystem::Transfers);
+        let spender = env::predecessor_account_id();
+        require!(
+            self.internal_has_role(&spender, ROLE_FINANCE),
+            DaoError::FinanceRoleRequired.with_detail(&spender)
+        );
+        let mut allowance = self
+            .allowances
+            .get(&spender)
+            .unwrap_or_else(|| DaoError::AllowanceNotFound.panic_with(&spender));
+
+        let now = env::block_timestamp_ms() / 1000;
+        let elapsed_periods = now.saturating_sub(allowance.period_start) / allowance.period_sec;
+        if elapsed_periods > 0 {
+            allowance.period_start += elapsed_periods * allowance.period_sec;
+            allowance.spent = U128(0);
+        }
+
+        require!(amount.0 <= allowance.per_tx_max.0, DaoError::TransactionLimitExceeded.to_string());
+        require!(
+            allowance.spent.0 + amount.0 <= allowance.per_period_max.0,
+            DaoError::AllowanceExceeded.to_string()
+        );
+        if let Some(recipients) = allowance.allowed_recipients.as_ref() {
+            require!(recipients.contains(&recipient), DaoError::RecipientNotAllowed.with_detail(&recipient));
+        }
+
+        allowance.spent = U128(allowance.spent.0 + amount.0);
+        self.allowances.insert(&spender, &allowance);
+
+        if self.accounts.get(&recipient).is_none() {
+            self.internal_register_account(&recipient);
+        }
+        let treasury_account_id = self.treasury_account_id();
+        self.internal_transfer(
+            &treasury_account_id,
+            &recipient,
+            NearToken::from_yoctonear(amount.0),
+            Some(memo.clone()),
+        );
+
+        let record = FinanceSpendRecord {
+            spender,
+            recipient,
+            amount,
+            memo,
+            timestamp: now,
+        };
+        FinanceSpend { record: &record }.emit();
+        self.finance_spends.push(&record);
+    }
+
+    pub fn get_allowance(&self, account_id: AccountId) -> Option<Allowance> {
+        self.allowances.get(&account_id)
+    }
+
+    pub fn get_allowances(&self) -> Vec<Allowance> {
+        self.allowances.values().collect()
+    }
+
+    /// Alle Ausgaben über finance_spend (paginiert) zur nachträglichen Prüfung
+    pub fn get_finance_spends(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<FinanceSpendRecord> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(50);
+        (from_index..std::cmp::min(from_index.saturating_add(limit), self.finance_spends.len()))
+            .filter_map(|index| self.finance_spends.get(index))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    const DAY_SEC: u64 = 24 * 60 * 60;
+
+    fn spend(contract: &mut Contract, recipient: &AccountId, amount: u128, now_ms: u64) {
+        set_context(&account("bob"), ONE_YOCTO, now_ms);
+        contract.finance_spend(recipient.clone(), U128(amount), "Office supplies".to_string());
+    }
+
+    fn setup_allowance(allowed_recipients: Option<Vec<AccountId>>) -> Contract {
+        let mut contract = setup();
+        let bob = account("bob");
+        contract.internal_register_account(&bob);
+        contract.internal_grant_role(&bob, ROLE_FINANCE, None, None);
+        contract.internal_set_allowance(bob, U128(50), U128(100), DAY_SEC, allowed_recipients);
+        contract
+    }
+
+    #[test]
+    fn spends_are_debited_from_treasury_and_logged() {
+        let mut contract = setup_allowance(None);
+        let carol = account("carol");
+        let treasury_before = contract.accounts.get(&treasury()).unwrap().as_yoctonear();
+
+        spend(&mut contract, &carol, 40, START_MS);
+        spend(&mut contract, &carol, 50, START_MS);
+
+        assert_eq!(contract.accounts.get(&carol).unwrap().as_yoctonear(), 90);
+        assert_eq!(contract.accounts.get(&treasury()).unwrap().as_yoctonear(), treasury_before - 90);
+        assert_eq!(contract.get_allowance(account("bob")).unwrap().spent, U128(90));
+        assert_eq!(contract.get_finance_spends(None, None).len(), 2);
+        assert_eq!(contract.get_finance_spends(Some(1), Some(u64::MAX)).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "TransactionLimitExceeded")]
+    fn per_transaction_limit_is_enforced() {
+        let mut contract = setup_allowance(None);
+        spend(&mut contract, &account("carol"), 51, START_MS);
+    }
+
+    #[test]
+    #[should_panic(expected = "AllowanceExceeded")]
+    fn period_limit_is_enforced() {
+        let mut contract = setup_allowance(None);
+        let carol = account("carol");
+        spend(&mut contract, &carol, 50, START_MS);
+        spend(&mut contract, &carol, 50, START_MS);
+        spend(&mut contract, &carol, 1, START_MS);
+    }
+
+    #[test]
+    fn limit_resets_in_the_next_period() {
+        let mut contract = setup_allowance(None);
+        let carol = account("carol");
+        spend(&mut contract, &carol, 50, START_MS);
+        spend(&mut contract, &carol, 50, START_MS);
+
+        spend(&mut contract, &carol, 50, START_MS + 2 * DAY_SEC * 1000 + 1);
+        let allowance = contract.get_allowance(account("bob")).unwrap();
+        assert_eq!(allowance.spent, U128(50));
+        assert_eq!(allowance.period_start, START_MS / 1000 + 2 * DAY_SEC);
+    }
+
+    #[test]
+    #[should_panic(expected = "RecipientNotAllowed")]
+    fn recipients_can_be_restricted() {
+        let mut contract = setup_allowance(Some(vec![account("carol")]));
+        spend(&mut contract, &account("carol"), 10, START_MS);
+        spend(&mut contract, &account("dave"), 10, START_MS);
+    }
+
+    #[test]
+    #[should_panic(expected = "FinanceRoleRequired")]
+    fn revoked_finance_role_blocks_spending() {
+        let mut contract = setup_allowance(None);
+        contract.internal_revoke_role(&account("bob"), ROLE_FINANCE, None);
+        spend(&mut contract, &account("carol"), 10, START_MS);
+    }
+}
@@ -0,0 +1,499 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::Gas;
+
+use crate::treasury::{is_promise_success, Asset};
+use crate::*;
+
+const GAS_FOR_BOUNTY_CALLBACK: Gas = Gas::from_tgas(10);
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, PartialEq, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum ClaimStatus {
+    /// Reserviert, Nachweis steht noch aus (verfällt nach `expires_at`)
+    Claimed,
+    Submitted,
+    Approved,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct BountyClaim {
+    pub account_id: AccountId,
+    pub claimed_at: u64,
+    pub expires_at: u64,
+    pub status: ClaimStatus,
+    pub proof: Option<String>,
+}
+
+impl BountyClaim {
+    /// Abgelaufene Reservierungen ohne Nachweis belegen keinen Platz mehr
+    pub fn is_live(&self, now: u64) -> bool {
+        self.status != ClaimStatus::Claimed || now < self.expires_at
+    }
+}
+
+/// Aufgabe für die Community. `amount` wird pro genehmigter Einreichung ausgezahlt,
+/// `amount * max_claims` beim Erstellen aus der Treasury reserviert. Zeitangaben in Sekunden.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Bounty {
+    pub id: u64,
+    pub description: String,
+    pub asset: Asset,
+    pub amount: U128,
+    pub max_claims: u32,
+    pub deadline: u64,
+    pub claim_period_sec: u64,
+    pub claims: Vec<BountyClaim>,
+    pub cancelled: bool,
+}
+
+impl Bounty {
+    fn live_claims(&self, now: u64) -> usize {
+        self.claims.iter().filter(|claim| claim.is_live(now)).count()
+    }
+
+    fn approved_claims(&self) -> u32 {
+        self.claims
+            .iter()
+            .filter(|claim| claim.status == ClaimStatus::Approved)
+            .count() as u32
+    }
+
+    pub fn is_open(&self, now: u64) -> bool {
+        !self.cancelled && now < self.deadline && self.live_claims(now) < self.max_claims as usize
+    }
+
+    fn claim_index(&self, account_id: &AccountId, now: u64) -> Option<usize> {
+        self.claims
+            .iter()
+            .position(|claim| &claim.account_id == account_id && claim.is_live(now))
+    }
+}
+
+fn now_sec() -> u64 {
+    env::block_timestamp_ms() / 1000
+}
+
+/// Gesamtreservierung `amount * max_claims`; bricht bei Überlauf mit BountyTotalOverflow ab
+pub(crate) fn bounty_total(amount: U128, max_claims: u32) -> u128 {
+    amount
+        .0
+        .checked_mul(max_claims as u128)
+        .unwrap_or_else(|| DaoError::BountyTotalOverflow.panic())
+}
+
+impl Contract {
+    pub(crate) fn internal_create_bounty(
+        &mut self,
+        description: String,
+        asset: Asset,
+        amount: U128,
+        max_claims: u32,
+        deadline: u64,
+        claim_period_sec: u64,
+    ) {
+        require!(amount.0 > 0, DaoError::InvalidBountyAmount.to_string());
+        require!(max_claims > 0, DaoError::InvalidBountyClaims.to_string());
+        require!(claim_period_sec > 0, DaoError::InvalidClaimPeriod.to_string());
+        require!(deadline > now_sec(), DaoError::InvalidBountyDeadline.to_string());
+
+        let id = self.next_bounty_id;
+        let bounty = Bounty {
+            id,
+            description,
+            asset,
+            amount,
+            max_claims,
+            deadline,
+            claim_period_sec,
+            claims: vec![],
+            cancelled: false,
+        };
+        self.internal_reserve_from_treasury(
+            &bounty.asset,
+            bounty_total(amount, max_claims),
+            format!("Bounty #{} funding", id),
+        );
+
+        self.bounties.insert(&id, &bounty);
+        self.next_bounty_id += 1;
+        BountyCreated { bounty: &bounty }.emit();
+    }
+
+    /// Genehmigt eine Einreichung und zahlt den Bounty-Betrag aus
+    pub(crate) fn internal_approve_bounty_claim(&mut self, bounty_id: u64, claimant: AccountId) {
+        let mut bounty = self
+            .bounties
+            .get(&bounty_id)
+            .unwrap_or_else(|| DaoError::BountyNotFound.panic_with(bounty_id));
+        require!(!bounty.cancelled, DaoError::BountyCancelled.with_detail(bounty_id));
+        let index = bounty
+            .claims
+            .iter()
+            .position(|claim| claim.account_id == claimant && claim.status == ClaimStatus::Submitted)
+            .unwrap_or_else(|| DaoError::ClaimNotFound.panic_with(&claimant));
+        bounty.claims[index].status = ClaimStatus::Approved;
+        self.bounties.insert(&bounty_id, &bounty);
+
+        let memo = format!("Bounty #{}", bounty_id);
+        if let Some(transfer) = self.internal_pay_reserved(&bounty.asset, &claimant, bounty.amount.0, memo) {
+            transfer
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_BOUNTY_CALLBACK)
+                        .on_bounty_payout(bounty_id, claimant),
+                )
+                .detach();
+        } else {
+            BountyClaimReviewed {
+                bounty_id,
+                claimant: &claimant,
+                approved: true,
+                amount: Some(bounty.amount),
+            }
+            .emit();
+        }
+    }
+
+    /// Schliesst die Bounty; nicht ausgezahlte Beträge gehen an die Treasury zurück
+    pub(crate) fn internal_cancel_bounty(&mut self, bounty_id: u64) {
+        let mut bounty = self
+            .bounties
+            .get(&bounty_id)
+            .unwrap_or_else(|| DaoError::BountyNotFound.panic_with(bounty_id));
+        require!(!bounty.cancelled, DaoError::BountyCancelled.with_detail(bounty_id));
+        bounty.cancelled = true;
+        let remainder = bounty_total(bounty.amount, bounty.max_claims - bounty.approved_claims());
+        if remainder > 0 {
+            self.internal_return_to_treasury(&bounty.asset, remainder, format!("Bounty #{} cancelled", bounty_id));
+        }
+        self.bounties.insert(&bounty_id, &bounty);
+        BountyCancelled {
+            bounty_id,
+            refunded: U128(remainder),
+        }
+        .emit();
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Reserviert einen Platz der Bounty für den Aufrufer (nur Mitglieder, keine Visitors)
+    pub fn claim_bounty(&mut self, bounty_id: u64) {
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.internal_roles_of(&account_id).iter().any(|role| role != ROLE_VISITOR),
+            DaoError::MembersOnly.to_string()
+        );
+        let mut bounty = self
+            .bounties
+            .get(&bounty_id)
+            .unwrap_or_else(|| DaoError::BountyNotFound.panic_with(bounty_id));
+        let now = now_sec();
+        require!(bounty.is_open(now), DaoError::BountyNotOpen.with_detail(bounty_id));
+        require!(
+            bounty.claim_index(&account_id, now).is_none(),
+            DaoError::AlreadyClaimed.to_string()
+        );
+
+        // Abgelaufene Reservierungen entfernen
+        bounty.claims.retain(|claim| claim.is_live(now));
+        let expires_at = std::cmp::min(now + bounty.claim_period_sec, bounty.deadline);
+        BountyClaimed {
+            bounty_id,
+            account_id: &account_id,
+            expires_at,
+        }
+        .emit();
+        bounty.claims.push(BountyClaim {
+            account_id,
+            claimed_at: now,
+            expires_at,
+            status: ClaimStatus::Claimed,
+            proof: None,
+        });
+        self.bounties.insert(&bounty_id, &bounty);
+    }
+
+    /// Reicht den Nachweis für eine reservierte Bounty ein
+    pub fn submit_bounty_done(&mut self, bounty_id: u64, proof: String) {
+        let account_id = env::predecessor_account_id();
+        let mut bounty = self
+            .bounties
+            .get(&bounty_id)
+            .unwrap_or_else(|| DaoError::BountyNotFound.panic_with(bounty_id));
+        require!(!bounty.cancelled, DaoError::BountyCancelled.with_detail(bounty_id));
+        let index = bounty
+            .claim_index(&account_id, now_sec())
+            .unwrap_or_else(|| DaoError::ClaimNotFound.panic_with(&account_id));
+        let claim = &mut bounty.claims[index];
+        require!(claim.status == ClaimStatus::Claimed, DaoError::ClaimAlreadySubmitted.to_string());
+        BountySubmitted {
+            bounty_id,
+            account_id: &account_id,
+            proof: &proof,
+        }
+        .emit();
+        claim.status = ClaimStatus::Submitted;
+        claim.proof = Some(proof);
+        self.bounties.insert(&bounty_id, &bounty);
+    }
+
+    /// Genehmigung durch einen Reviewer (alternativ per ApproveBountyClaim-Proposal)
+    pub fn approve_bounty_claim(&mut self, bounty_id: u64, claimant: AccountId) {
+        self.require_permission(&env::predecessor_account_id(), Permission::ReviewBounties);
+        self.internal_approve_bounty_claim(bounty_id, claimant);
+    }
+
+    /// Weist eine Einreichung zurück und gibt den Platz wieder frei
+    pub fn reject_bounty_claim(&mut self, bounty_id: u64, claimant: AccountId) {
+        self.require_permission(&env::predecessor_account_id(), Permission::ReviewBounties);
+        let mut bounty = self
+            .bounties
+            .get(&bounty_id)
+            .unwrap_or_else(|| DaoError::BountyNotFound.panic_with(bounty_id));
+        let before = bounty.claims.len();
+        bounty
+            .claims
+            .retain(|claim| !(claim.account_id == claimant && claim.status == ClaimStatus::Submitted));
+        require!(bounty.claims.len() < before, DaoError::ClaimNotFound.with_detail(&claimant));
+        self.bounties.insert(&bounty_id, &bounty);
+        BountyClaimReviewed {
+            bounty_id,
+            claimant: &claimant,
+            approved: false,
+            amount: None,
+        }
+        .emit();
+    }
+
+    /// Meldet die Genehmigung nach erfolgter Auszahlung bzw. setzt die Einreichung bei
+    /// fehlgeschlagener Überweisung wieder auf Submitted
+    #[private]
+    pub fn on_bounty_payout(&mut self, bounty_id: u64, claimant: AccountId) -> bool {
+        let succeeded = is_promise_success();
+        if succeeded {
+            if let Some(bounty) = self.bounties.get(&bounty_id) {
+                BountyClaimReviewed {
+                    bounty_id,
+                    claimant: &claimant,
+                    approved: true,
+                    amount: Some(bounty.amount),
+                }
+                .emit();
+            }
+        } else {
+            if let Some(mut bounty) = self.bounties.get(&bounty_id) {
+                if let Some(claim) = bounty
+                    .claims
+                    .iter_mut()
+                    .find(|claim| claim.account_id == claimant && claim.status == ClaimStatus::Approved)
+                {
+                    claim.status = ClaimStatus::Submitted;
+                }
+                if bounty.cancelled {
+                    self.internal_return_to_treasury(
+                        &bounty.asset,
+                        bounty.amount.0,
+                        format!("Bounty #{} cancelled", bounty_id),
+                    );
+                }
+                self.bounties.insert(&bounty_id, &bounty);
+            }
+            env::log_str(&format!("Payout for bounty #{} to {} failed", bounty_id, claimant));
+        }
+        succeeded
+    }
+
+    pub fn get_bounty(&self, bounty_id: u64) -> Option<Bounty> {
+        self.bounties.get(&bounty_id)
+    }
+
+    /// Bounties, die aktuell noch beansprucht werden können
+    pub fn get_open_bounties(&self) -> Vec<Bounty> {
+        let now = now_sec();
+        self.bounties.values().filter(|bounty| bounty.is_open(now)).collect()
+    }
+
+    /// Bounties mit laufenden (nicht abgelaufenen, noch nicht ausgezahlten) Claims,
+    /// optional nur die eines bestimmten Accounts
+    pub fn get_claimed_bounties(&self, account_id: Option<AccountId>) -> Vec<Bounty> {
+        let now = now_sec();
+        self.bounties
+            .values()
+            .filter(|bounty| {
+                !bounty.cancelled
+                    && bounty.claims.iter().any(|claim| {
+                        claim.is_live(now)
+                            && claim.status != ClaimStatus::Approved
+                            && account_id.as_ref().is_none_or(|account_id| &claim.account_id == account_id)
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    const START: u64 = START_MS / 1000;
+    const REWARD: u128 = 1_000;
+    const CLAIM_PERIOD: u64 = 3_600;
+
+    fn dao_token() -> Asset {
+        Asset::Ft { token_id: dao() }
+    }
+
+    fn token_balance(contract: &Contract, account_id: &AccountId) -> u128 {
+        contract.accounts.get(account_id).unwrap_or(ZERO_TOKEN).as_yoctonear()
+    }
+
+    /// Bounty mit drei Plätzen; Bob und Carol sind Community-Mitglieder
+    fn setup_bounty(asset: Asset) -> Contract {
+        let mut contract = setup();
+        if asset == Asset::Near {
+            set_context(&account("donor"), NearToken::from_near(10), START_MS);
+            contract.donate();
+        }
+        for name in ["bob", "carol"] {
+            contract.internal_register_account(&account(name));
+            contract.internal_grant_role(&account(name), ROLE_COMMUNITY, None, None);
+        }
+        contract.internal_create_bounty(
+            "Write docs".to_string(),
+            asset,
+            U128(REWARD),
+            3,
+            START + 7 * 24 * 3600,
+            CLAIM_PERIOD,
+        );
+        contract
+    }
+
+    fn claim_and_submit(contract: &mut Contract, name: &str) {
+        set_context(&account(name), ZERO_TOKEN, START_MS);
+        contract.claim_bounty(0);
+        contract.submit_bounty_done(0, "https://example.org/pr/1".to_string());
+    }
+
+    fn approve(contract: &mut Contract, name: &str) {
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.internal_approve_bounty_claim(0, account(name));
+    }
+
+    #[test]
+    fn approval_pays_the_reward_from_escrow() {
+        let mut contract = setup_bounty(dao_token());
+        assert_eq!(contract.escrow_tokens.as_yoctonear(), 3 * REWARD);
+
+        claim_and_submit(&mut contract, "bob");
+        approve(&mut contract, "bob");
+        assert_eq!(event_count("bounty_claim_reviewed"), 1);
+        assert_eq!(token_balance(&contract, &account("bob")), REWARD);
+        assert_eq!(contract.escrow_tokens.as_yoctonear(), 2 * REWARD);
+    }
+
+    #[test]
+    #[should_panic(expected = "BountyTotalOverflow")]
+    fn overflowing_bounty_proposal_is_rejected_on_creation() {
+        let mut contract = setup();
+        set_context(&team(), ZERO_TOKEN, START_MS);
+        let kind = ProposalKind::CreateBounty {
+            description: "Too big".to_string(),
+            asset: dao_token(),
+            amount: U128(u128::MAX / 2),
+            max_claims: 3,
+            deadline: START + 3600,
+            claim_period_sec: CLAIM_PERIOD,
+        };
+        contract.create_proposal(
+            "Bounty".to_string(),
+            String::new(),
+            None,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(kind),
+        );
+    }
+
+    #[test]
+    fn cancel_refunds_all_unpaid_places() {
+        let mut contract = setup_bounty(dao_token());
+        claim_and_submit(&mut contract, "bob");
+        approve(&mut contract, "bob");
+        // Eingereicht, aber nicht genehmigt: zählt zum Rest
+        claim_and_submit(&mut contract, "carol");
+
+        contract.internal_cancel_bounty(0);
+        assert_eq!(event_count("bounty_cancelled"), 1);
+        assert_eq!(token_balance(&contract, &treasury()), 3_000_000 * ONE_TOKEN - REWARD);
+        assert_eq!(contract.escrow_tokens, ZERO_TOKEN);
+        assert!(contract.get_open_bounties().is_empty());
+    }
+
+    #[test]
+    fn failed_payout_keeps_the_reward_reserved() {
+        let mut contract = setup_bounty(Asset::Near);
+        let ledger_after_funding = contract.internal_treasury_balance(&Asset::Near);
+        claim_and_submit(&mut contract, "bob");
+        approve(&mut contract, "bob");
+        assert_eq!(near_transfers(), vec![(account("bob"), REWARD)]);
+
+        set_callback_context(false, START_MS);
+        assert!(!contract.on_bounty_payout(0, account("bob")));
+        assert_eq!(contract.get_bounty(0).unwrap().claims[0].status, ClaimStatus::Submitted);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding);
+
+        // Nach dem Abbruch gehen alle drei Plätze an die Treasury zurück
+        contract.internal_cancel_bounty(0);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding + 3 * REWARD);
+    }
+
+    #[test]
+    fn failed_payout_after_cancel_returns_the_reward() {
+        let mut contract = setup_bounty(Asset::Near);
+        let ledger_after_funding = contract.internal_treasury_balance(&Asset::Near);
+        claim_and_submit(&mut contract, "bob");
+        approve(&mut contract, "bob");
+        contract.internal_cancel_bounty(0);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding + 2 * REWARD);
+
+        set_callback_context(false, START_MS);
+        assert!(!contract.on_bounty_payout(0, account("bob")));
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding + 3 * REWARD);
+    }
+
+    #[test]
+    fn expired_claims_free_their_place() {
+        let mut contract = setup_bounty(dao_token());
+        set_context(&account("bob"), ZERO_TOKEN, START_MS);
+        contract.claim_bounty(0);
+        assert_eq!(contract.get_claimed_bounties(Some(account("bob"))).len(), 1);
+
+        set_context(&account("bob"), ZERO_TOKEN, (START + CLAIM_PERIOD) * 1000);
+        assert!(contract.get_claimed_bounties(Some(account("bob"))).is_empty());
+        contract.claim_bounty(0);
+        assert_eq!(contract.get_bounty(0).unwrap().claims.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "MembersOnly")]
+    fn visitors_cannot_claim() {
+        let mut contract = setup_bounty(dao_token());
+        contract.internal_register_account(&account("dave"));
+        set_context(&account("dave"), ZERO_TOKEN, START_MS);
+        contract.claim_bounty(0);
+    }
+}
@@ -0,0 +1,244 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+use crate::treasury::Asset;
+use crate::*;
+
+/// Budget einer Kategorie (z.B. marketing, development) für ein Asset und eine Periode.
+/// Zeitangaben in Sekunden.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Budget {
+    pub category: String,
+    pub asset: Asset,
+    /// Freigegebener Betrag pro Periode
+    pub amount: U128,
+    pub period_sec: u64,
+    pub period_start: u64,
+    pub spent: U128,
+    /// Nicht genutzte Beträge werden in die nächste Periode übertragen
+    pub rollover: bool,
+    pub carried_over: U128,
+}
+
+impl Budget {
+    /// Setzt die Periode zurück bzw. überträgt Restbeträge, falls Periodengrenzen überschritten wurden
+    fn roll(&mut self, now_sec: u64) {
+        let elapsed_periods = now_sec.saturating_sub(self.period_start) / self.period_sec;
+        if elapsed_periods == 0 {
+            return;
+        }
+        // Sättigend: sehr grosse Budgets oder lange Pausen dürfen den View nicht zum Absturz bringen
+        self.carried_over = if self.rollover {
+            let skipped = self.amount.0.saturating_mul(elapsed_periods as u128 - 1);
+            U128(self.available().saturating_add(skipped))
+        } else {
+            U128(0)
+        };
+        self.spent = U128(0);
+        self.period_start = self
+            .period_start
+            .saturating_add(elapsed_periods.saturating_mul(self.period_sec));
+    }
+
+    pub fn available(&self) -> u128 {
+        self.amount.0.saturating_add(self.carried_over.0).saturating_sub(self.spent.0)
+    }
+}
+
+fn now_sec() -> u64 {
+    env::block_timestamp_ms() / 1000
+}
+
+impl Contract {
+    pub(crate) fn internal_set_budget(
+        &mut self,
+        category: String,
+        asset: Asset,
+        amount: U128,
+        period_sec: u64,
+        rollover: bool,
+    ) {
+        require!(!category.is_empty(), DaoError::EmptyBudgetCategory.to_string());
+        require!(period_sec > 0, DaoError::InvalidBudgetPeriod.to_string());
+        let key = (category.clone(), asset.clone());
+        // Bereits ausgegebene Beträge der laufenden Periode bleiben erhalten
+        let (period_start, spent, carried_over) = match self.budgets.get(&key) {
+            Some(mut budget) => {
+                budget.roll(now_sec());
+                (budget.period_start, budget.spent, budget.carried_over)
+            }
+            None => (now_sec(), U128(0), U128(0)),
+        };
+        let budget = Budget {
+            category,
+            asset,
+            amount,
+            period_sec,
+            period_start,
+            spent,
+            rollover,
+            carried_over,
+        };
+        self.budgets.insert(&key, &budget);
+    }
+
+    pub(crate) fn internal_remove_budget(&mut self, category: String, asset: Asset) {
+        require!(
+            self.budgets.remove(&(category, asset)).is_some(),
+            DaoError::BudgetNotFound.to_string()
+        );
+    }
+
+    /// Bucht eine kategorisierte Ausgabe gegen das passende Budget.
+    /// Kategorien ohne Budget für dieses Asset bleiben unbeschränkt.
+    pub(crate) fn internal_budget_spend(&mut self, category: &str, asset: &Asset, amount: u128) {
+        let key = (category.to_string(), asset.clone());
+        let Some(mut budget) = self.budgets.get(&key) else {
+            return;
+        };
+        budget.roll(now_sec());
+        require!(amount <= budget.available(), DaoError::BudgetExceeded.to_string());
+        budget.spent = U128(budget.spent.0.saturating_add(amount));
+        self.budgets.insert(&key, &budget);
+    }
+
+    /// Gibt eine Ausgabe wieder frei (z.B. nach fehlgeschlagener Auszahlung)
+    pub(crate) fn internal_budget_refund(&mut self, category: &str, asset: &Asset, amount: u128) {
+        let key = (category.to_string(), asset.clone());
+        if let Some(mut budget) = self.budgets.get(&key) {
+            budget.spent = U128(budget.spent.0.saturating_sub(amount));
+            self.budgets.insert(&key, &budget);
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Alle Budgets mit aktuellem Verbrauch (Perioden bereits fortgeschrieben)
+    pub fn get_budgets(&self) -> Vec<Budget> {
+        self.budgets
+            .values()
+            .map(|mut budget| {
+                budget.roll(now_sec());
+                budget
+            })
+            .collect()
+    }
+
+    pub fn get_budget(&self, category: String, asset: Asset) -> Option<Budget> {
+        self.budgets.get(&(category, asset)).map(|mut budget| {
+            budget.roll(now_sec());
+            budget
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    const PERIOD_SEC: u64 = 30 * 24 * 60 * 60;
+
+    fn set_time(now_sec: u64) {
+        set_context(&owner(), ZERO_TOKEN, now_sec * 1000);
+    }
+
+    fn budget(contract: &Contract) -> Budget {
+        contract.get_budget("dev".to_string(), Asset::Near).unwrap()
+    }
+
+    fn setup_budget(rollover: bool) -> (Contract, u64) {
+        let mut contract = setup();
+        let start = START_MS / 1000;
+        contract.internal_set_budget("dev".to_string(), Asset::Near, U128(100), PERIOD_SEC, rollover);
+        (contract, start)
+    }
+
+    #[test]
+    fn unused_amount_is_carried_over() {
+        let (mut contract, start) = setup_budget(true);
+        contract.internal_budget_spend("dev", &Asset::Near, 30);
+
+        set_time(start + PERIOD_SEC);
+        let rolled = budget(&contract);
+        assert_eq!(rolled.carried_over, U128(70));
+        assert_eq!(rolled.spent, U128(0));
+        assert_eq!(rolled.period_start, start + PERIOD_SEC);
+        assert_eq!(rolled.available(), 170);
+
+        contract.internal_budget_spend("dev", &Asset::Near, 170);
+        assert_eq!(budget(&contract).available(), 0);
+    }
+
+    #[test]
+    fn skipped_periods_accumulate_with_rollover() {
+        let (mut contract, start) = setup_budget(true);
+        contract.internal_budget_spend("dev", &Asset::Near, 40);
+
+        // Zwei volle Perioden ohne Ausgaben: Rest der ersten + komplette zweite Periode
+        set_time(start + 2 * PERIOD_SEC + 5);
+        let rolled = budget(&contract);
+        assert_eq!(rolled.carried_over, U128(60 + 100));
+        assert_eq!(rolled.period_start, start + 2 * PERIOD_SEC);
+        assert_eq!(rolled.available(), 260);
+    }
+
+    #[test]
+    fn huge_rollover_saturates_instead_of_overflowing() {
+        let mut contract = setup();
+        let start = START_MS / 1000;
+        contract.internal_set_budget("dev".to_string(), Asset::Near, U128(u128::MAX / 2), PERIOD_SEC, true);
+
+        set_time(start + 5 * PERIOD_SEC);
+        let rolled = budget(&contract);
+        assert_eq!(rolled.available(), u128::MAX);
+        assert_eq!(rolled.period_start, start + 5 * PERIOD_SEC);
+        contract.internal_budget_spend("dev", &Asset::Near, u128::MAX);
+        assert_eq!(budget(&contract).available(), 0);
+    }
+
+    #[test]
+    fn without_rollover_the_period_resets() {
+        let (mut contract, start) = setup_budget(false);
+        contract.internal_budget_spend("dev", &Asset::Near, 30);
+
+        set_time(start + 3 * PERIOD_SEC);
+        let rolled = budget(&contract);
+        assert_eq!(rolled.carried_over, U128(0));
+        assert_eq!(rolled.available(), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "BudgetExceeded")]
+    fn spending_above_the_budget_fails() {
+        let (mut contract, _) = setup_budget(true);
+        contract.internal_budget_spend("dev", &Asset::Near, 60);
+        contract.internal_budget_spend("dev", &Asset::Near, 41);
+    }
+
+    #[test]
+    fn updating_a_budget_keeps_the_current_period() {
+        let (mut contract, start) = setup_budget(true);
+        contract.internal_budget_spend("dev", &Asset::Near, 30);
+
+        set_time(start + 10);
+        contract.internal_set_budget("dev".to_string(), Asset::Near, U128(50), PERIOD_SEC, true);
+        let updated = budget(&contract);
+        assert_eq!(updated.spent, U128(30));
+        assert_eq!(updated.period_start, start);
+        assert_eq!(updated.available(), 20);
+    }
+
+    #[test]
+    fn refund_releases_spent_amount() {
+        let (mut contract, _) = setup_budget(false);
+        contract.internal_budget_spend("dev", &Asset::Near, 80);
+        contract.internal_budget_refund("dev", &Asset::Near, 50);
+        assert_eq!(budget(&contract).spent, U128(30));
+        // Kategorien ohne Budget bleiben unbeschränkt
+        contract.internal_budget_spend("marketing", &Asset::Near, 1_000);
+    }
+}
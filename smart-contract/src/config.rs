@@ -0,0 +1,382 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::upgrade::DEFAULT_UPGRADE_TIMELOCK_SEC;
+use crate::*;
+
+/// Mindestdauer einer Abstimmung, falls eine Voting-Periode konfiguriert wird
+pub const MIN_VOTING_PERIOD_SEC: u64 = 60 * 60; // 1 Stunde
+/// Standard-Gültigkeit einer Multisig-Freigabe
+pub const DEFAULT_APPROVAL_TTL_SEC: u64 = 3 * 24 * 60 * 60; // 3 Tage
+/// Standard-Anzahl Core-Freigaben für eine Notfall-Pause
+pub const DEFAULT_PAUSE_QUORUM: u32 = 2;
+
+/// Zentrale, versionierte DAO-Konfiguration. Änderungen nur über ein UpdateConfig-Proposal.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct DaoConfig {
+    /// Wird bei jeder Änderung erhöht
+    pub version: u32,
+    pub treasury_account_id: AccountId,
+    pub team_account_id: AccountId,
+    /// Tokens pro yoctoNEAR beim Verkauf (buy_tokens)
+    pub sale_rate: U128,
+    /// Mindest-Deposit (yoctoNEAR) beim Erstellen eines Proposals
+    pub proposal_bond: U128,
+    /// Standard-Abstimmungsdauer in Sekunden, falls ein Proposal keine Deadline angibt
+    pub voting_period_sec: Option<u64>,
+    /// Standard-Quorum (Summe der Stimmgewichte), falls ein Proposal keines angibt
+    pub default_quorum: Option<U128>,
+    /// Token-Schwellen für automatische Rollen (Rolle, Mindestguthaben in Yocto)
+    pub role_thresholds: Vec<(String, U128)>,
+    /// Anzahl benötigter Freigaben (M) für execute_proposal / distribute_dividends; 1 = Single-Signer
+    pub multisig_threshold: u32,
+    /// Signer-Set (N); leer = alle Accounts mit der jeweiligen Berechtigung
+    pub multisig_signers: Vec<AccountId>,
+    /// Gültigkeit einer Freigabe in Sekunden
+    pub approval_ttl_sec: u64,
+    /// Accounts, die Teilbereiche ohne weitere Freigaben pausieren dürfen
+    pub guardians: Vec<AccountId>,
+    /// Anzahl Core-Freigaben für eine Pause ohne Guardian
+    pub pause_quorum: u32,
+    /// Wartezeit in Sekunden zwischen Ausführung eines Upgrade-Proposals und apply_upgrade
+    pub upgrade_timelock_sec: u64,
+}
+
+impl DaoConfig {
+    pub fn new(treasury_account_id: AccountId, team_account_id: AccountId) -> Self {
+        Self {
+            version: 1,
+            treasury_account_id,
+            team_account_id,
+            sale_rate: U128(NEAR_TO_CHF_RATE),
+            proposal_bond: U128(0),
+            voting_period_sec: None,
+            default_quorum: None,
+            role_thresholds: vec![(ROLE_COMMUNITY.to_string(), U128(DEFAULT_COMMUNITY_THRESHOLD))],
+            multisig_threshold: 1,
+            multisig_signers: vec![],
+            approval_ttl_sec: DEFAULT_APPROVAL_TTL_SEC,
+            guardians: vec![],
+            pause_quorum: DEFAULT_PAUSE_QUORUM,
+            upgrade_timelock_sec: DEFAULT_UPGRADE_TIMELOCK_SEC,
+        }
+    }
+}
+
+/// Teil-Update der Konfiguration. Nicht gesetzte Felder bleiben unverändert;
+/// `Some(0)` bei voting_period_sec / default_quorum entfernt den Standardwert.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, PartialEq, Debug, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+#[schemars(crate = "schemars")]
+pub struct ConfigUpdate {
+    #[schemars(with = "Option<String>")]
+    pub sale_rate: Option<U128>,
+    #[schemars(with = "Option<String>")]
+    pub proposal_bond: Option<U128>,
+    pub voting_period_sec: Option<u64>,
+    #[schemars(with = "Option<String>")]
+    pub default_quorum: Option<U128>,
+    #[schemars(with = "Option<Vec<(String, String)>>")]
+    pub role_thresholds: Option<Vec<(String, U128)>>,
+    pub multisig_threshold: Option<u32>,
+    #[schemars(with = "Option<Vec<String>>")]
+    pub multisig_signers: Option<Vec<AccountId>>,
+    pub approval_ttl_sec: Option<u64>,
+    #[schemars(with = "Option<Vec<String>>")]
+    pub guardians: Option<Vec<AccountId>>,
+    pub pause_quorum: Option<u32>,
+    pub upgrade_timelock_sec: Option<u64>,
+}
+
+impl Contract {
+    pub(crate) fn treasury_account_id(&self) -> AccountId {
+        self.config.treasury_account_id.clone()
+    }
+
+    pub(crate) fn team_account_id(&self) -> AccountId {
+        self.config.team_account_id.clone()
+    }
+
+    /// Validiert jedes Feld einzeln, übernimmt die Änderungen und erhöht die Version
+    pub(crate) fn internal_update_config(&mut self, update: ConfigUpdate) {
+        let mut config = self.config.clone();
+        if let Some(sale_rate) = update.sale_rate {
+            require!(sale_rate.0 > 0, DaoError::InvalidSaleRate.to_string());
+            config.sale_rate = sale_rate;
+        }
+        if let Some(proposal_bond) = update.proposal_bond {
+            config.proposal_bond = proposal_bond;
+        }
+        if let Some(voting_period_sec) = update.voting_period_sec {
+            require!(
+                voting_period_sec == 0 || voting_period_sec >= MIN_VOTING_PERIOD_SEC,
+                DaoError::VotingPeriodTooShort.with_detail(MIN_VOTING_PERIOD_SEC)
+            );
+            config.voting_period_sec = Some(voting_period_sec).filter(|period| *period > 0);
+        }
+        if let Some(default_quorum) = update.default_quorum {
+            require!(
+                default_quorum.0 <= self.total_supply.as_yoctonear(),
+                DaoError::QuorumExceedsSupply.to_string()
+            );
+            config.default_quorum = Some(default_quorum).filter(|quorum| quorum.0 > 0);
+        }
+        if let Some(role_thresholds) = update.role_thresholds.clone() {
+            self.assert_valid_role_thresholds(&role_thresholds);
+            config.role_thresholds = role_thresholds;
+        }
+        if let Some(multisig_threshold) = update.multisig_threshold {
+            require!(multisig_threshold >= 1, DaoError::InvalidMultisigThreshold.to_string());
+            config.multisig_threshold = multisig_threshold;
+        }
+        if let Some(multisig_signers) = update.multisig_signers.clone() {
+            for signer in multisig_signers.iter() {
+                require!(
+                    self.internal_has_role(signer, ROLE_CORE) || self.internal_has_role(signer, ROLE_FINANCE),
+                    DaoError::InvalidMultisigSigner.with_detail(signer)
+                );
+            }
+            config.multisig_signers = multisig_signers;
+        }
+        if let Some(approval_ttl_sec) = update.approval_ttl_sec {
+            require!(approval_ttl_sec > 0, DaoError::InvalidApprovalTtl.to_string());
+            config.approval_ttl_sec = approval_ttl_sec;
+        }
+        if let Some(guardians) = update.guardians.clone() {
+            config.guardians = guardians;
+        }
+        if let Some(pause_quorum) = update.pause_quorum {
+            require!(pause_quorum >= 1, DaoError::InvalidPauseQuorum.to_string());
+            config.pause_quorum = pause_quorum;
+        }
+        if let Some(upgrade_timelock_sec) = update.upgrade_timelock_sec {
+            config.upgrade_timelock_sec = upgrade_timelock_sec;
+        }
+        require!(
+            config.multisig_signers.is_empty()
+                || config.multisig_threshold as usize <= config.multisig_signers.len(),
+            DaoError::MultisigThresholdExceedsSigners.to_string()
+        );
+        config.version += 1;
+        self.config = config;
+
+        ConfigUpdated {
+            version: self.config.version,
+            update: &update,
+        }
+        .emit();
+    }
+
+    /// Wechselt Treasury- und/oder Team-Account: die Guthaben der bisherigen Accounts werden übertragen
+    /// und die zugehörigen Rollen (finance bzw. core) umgehängt. Ein Tausch der beiden Accounts ist möglich.
+    /// Neue Accounts, die bisher weder Treasury noch Team waren, dürfen keine Tokens halten,
+    /// damit private Guthaben nicht mit DAO-Mitteln vermischt werden.
+    pub(crate) fn internal_change_dao_accounts(
+        &mut self,
+        treasury_account: Option<String>,
+        team_account: Option<String>,
+    ) {
+        let old_treasury = self.treasury_account_id();
+        let old_team = self.team_account_id();
+        let treasury_account_id = parse_dao_account(treasury_account).unwrap_or_else(|| old_treasury.clone());
+        let team_account_id = parse_dao_account(team_account).unwrap_or_else(|| old_team.clone());
+        require!(
+            treasury_account_id != team_account_id,
+            DaoError::TreasuryEqualsTeam.to_string()
+        );
+        for new_account in [&treasury_account_id, &team_account_id] {
+            if new_account != &old_treasury && new_account != &old_team {
+                require!(
+                    self.accounts.get(new_account).unwrap_or(ZERO_TOKEN).is_zero(),
+                    DaoError::DaoAccountNotEmpty.with_detail(new_account)
+                );
+            }
+        }
+
+        // Beide Guthaben vor der ersten Übertragung lesen: bei einem Tausch darf nur der jeweilige
+        // bisherige Betrag wandern, nicht das bereits übertragene Guthaben des anderen Accounts
+        let treasury_balance = self.accounts.get(&old_treasury).unwrap_or(ZERO_TOKEN);
+        let team_balance = self.accounts.get(&old_team).unwrap_or(ZERO_TOKEN);
+        self.config.treasury_account_id = treasury_account_id.clone();
+        self.config.team_account_id = team_account_id.clone();
+
+        if old_treasury != treasury_account_id {
+            self.internal_migrate_dao_account(
+                &old_treasury,
+                &treasury_account_id,
+                treasury_balance,
+                ROLE_FINANCE,
+                "Treasury account changed",
+            );
+        }
+        if old_team != team_account_id {
+            self.internal_migrate_dao_account(&old_team, &team_account_id, team_balance, ROLE_CORE, "Team account changed");
+        }
+    }
+
+    fn internal_migrate_dao_account(
+        &mut self,
+        old: &AccountId,
+        new: &AccountId,
+        balance: NearToken,
+        role: &str,
+        reason: &str,
+    ) {
+        if self.accounts.get(new).is_none() {
+            self.internal_register_account(new);
+        }
+        // Rolle zuerst vergeben, damit die Core-Invariante beim Entzug erfüllt bleibt
+        self.internal_grant_role(new, role, None, Some(reason.to_string()));
+
+        if balance.gt(&ZERO_TOKEN) {
+            self.internal_transfer(old, new, balance, Some(reason.to_string()));
+        }
+        if self.internal_has_role(old, role) {
+            self.internal_revoke_role(old, role, Some(reason.to_string()));
+        }
+    }
+}
+
+fn parse_dao_account(account: Option<String>) -> Option<AccountId> {
+    account.map(|account| {
+        account
+            .parse()
+            .unwrap_or_else(|_| DaoError::InvalidDaoAccount.panic_with(account))
+    })
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_config(&self) -> DaoConfig {
+        self.config.clone()
+    }
+
+    /// Erstellt ein UpdateConfig-Proposal. Die Einstellungen ändern sich erst nach Annahme und Ausführung.
+    #[payable]
+    pub fn update_settings(&mut self, update: ConfigUpdate) -> u64 {
+        self.create_proposal(
+            "Update DAO settings".to_string(),
+            format!("{:?}", update),
+            None,
+            vec!["settings".to_string()],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(ProposalKind::UpdateConfig { update }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proposal::ProposalStatus;
+    use crate::test_utils::*;
+
+    fn balance(contract: &Contract, account_id: &AccountId) -> u128 {
+        contract.accounts.get(account_id).unwrap_or(ZERO_TOKEN).as_yoctonear()
+    }
+
+    #[test]
+    fn swapping_treasury_and_team_keeps_balances() {
+        let mut contract = setup();
+        let treasury_balance = balance(&contract, &treasury());
+        let team_balance = balance(&contract, &team());
+        assert_ne!(treasury_balance, team_balance);
+
+        contract.internal_change_dao_accounts(Some(team().to_string()), Some(treasury().to_string()));
+
+        assert_eq!(contract.treasury_account_id(), team());
+        assert_eq!(contract.team_account_id(), treasury());
+        assert_eq!(balance(&contract, &team()), treasury_balance);
+        assert_eq!(balance(&contract, &treasury()), team_balance);
+        assert!(contract.internal_has_role(&team(), ROLE_FINANCE));
+        assert!(!contract.internal_has_role(&team(), ROLE_CORE));
+        assert!(contract.internal_has_role(&treasury(), ROLE_CORE));
+        assert!(!contract.internal_has_role(&treasury(), ROLE_FINANCE));
+    }
+
+    #[test]
+    fn moving_to_new_account_transfers_exact_balance() {
+        let mut contract = setup();
+        let treasury_balance = balance(&contract, &treasury());
+        let team_balance = balance(&contract, &team());
+        let new_team = account("new-team");
+
+        // Neuer Team-Account und bisheriger Team-Account wird Treasury
+        contract.internal_change_dao_accounts(Some(team().to_string()), Some(new_team.to_string()));
+
+        assert_eq!(balance(&contract, &team()), treasury_balance);
+        assert_eq!(balance(&contract, &new_team), team_balance);
+        assert_eq!(balance(&contract, &treasury()), 0);
+        assert!(contract.internal_has_role(&new_team, ROLE_CORE));
+        assert!(contract.internal_has_role(&team(), ROLE_FINANCE));
+    }
+
+    #[test]
+    #[should_panic(expected = "DaoAccountNotEmpty")]
+    fn new_account_with_tokens_is_rejected() {
+        let mut contract = setup();
+        let holder = account("holder");
+        fund(&mut contract, &holder, 10);
+        contract.internal_change_dao_accounts(Some(holder.to_string()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidDaoAccount")]
+    fn invalid_account_is_rejected() {
+        let mut contract = setup();
+        contract.internal_change_dao_accounts(None, Some("Not An Account".to_string()));
+    }
+
+    fn with_bond(contract: &mut Contract, deposit_near: u128) -> u64 {
+        contract.config.proposal_bond = U128(ONE_TOKEN);
+        set_context(&team(), NearToken::from_near(deposit_near), START_MS);
+        contract.update_settings(ConfigUpdate { voting_period_sec: Some(60), ..Default::default() })
+    }
+
+    #[test]
+    fn bond_is_held_and_overpayment_refunded_immediately() {
+        let mut contract = setup();
+        let proposal_id = with_bond(&mut contract, 3);
+
+        assert_eq!(near_transfers(), vec![(team(), 2 * ONE_TOKEN)]);
+        assert_eq!(contract.proposals.get(&proposal_id).unwrap().bond, U128(ONE_TOKEN));
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), 0);
+    }
+
+    #[test]
+    fn bond_is_refunded_when_quorum_is_reached() {
+        let mut contract = setup();
+        let proposal_id = with_bond(&mut contract, 1);
+        assert!(near_transfers().is_empty());
+
+        set_context(&team(), ONE_YOCTO, START_MS);
+        contract.vote_on_proposal(proposal_id, false);
+        set_context(&team(), ZERO_TOKEN, START_MS);
+        contract.finalize_proposal(proposal_id);
+
+        assert_eq!(contract.proposals.get(&proposal_id).unwrap().status, ProposalStatus::Rejected);
+        assert_eq!(near_transfers(), vec![(team(), ONE_TOKEN)]);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), 0);
+    }
+
+    #[test]
+    fn bond_without_quorum_goes_to_treasury() {
+        let mut contract = setup();
+        contract.config.default_quorum = Some(U128(ONE_TOKEN));
+        let proposal_id = with_bond(&mut contract, 1);
+
+        set_context(&team(), ZERO_TOKEN, START_MS);
+        contract.finalize_proposal(proposal_id);
+
+        assert!(near_transfers().is_empty());
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ONE_TOKEN);
+    }
+}
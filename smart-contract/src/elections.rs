@@ -0,0 +1,461 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::*;
+
+/// Stimmgewichtung bei Core-Wahlen
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, PartialEq, Debug, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+#[schemars(crate = "schemars")]
+pub enum VotingMode {
+    TokenWeighted,
+    OneMemberOneVote,
+}
+
+/// Eine Wahlperiode: Nominierungsfenster, danach Abstimmung über `seats` Core-Sitze.
+/// Die Sitze laufen bis `term_end` (ms), danach verfällt die gewählte Core-Rolle.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Election {
+    pub term_id: u64,
+    pub seats: u32,
+    pub mode: VotingMode,
+    pub nomination_end: u64,
+    pub voting_end: u64,
+    pub term_end: u64,
+    pub candidates: Vec<(AccountId, u128)>,
+    pub winners: Vec<AccountId>,
+    pub finalized: bool,
+}
+
+impl Contract {
+    pub(crate) fn internal_start_election(
+        &mut self,
+        seats: u32,
+        mode: VotingMode,
+        nomination_period_ms: u64,
+        voting_period_ms: u64,
+        term_ms: u64,
+    ) {
+        require!(seats > 0, DaoError::InvalidElectionSeats.to_string());
+        require!(
+            nomination_period_ms > 0 && voting_period_ms > 0 && term_ms > 0,
+            DaoError::InvalidElectionPeriods.to_string()
+        );
+        if let Some(current) = self.internal_last_election() {
+            require!(current.finalized, DaoError::ElectionNotFinalized.to_string());
+        }
+
+        let now = env::block_timestamp_ms();
+        let voting_end = now + nomination_period_ms + voting_period_ms;
+        let election = Election {
+            term_id: self.elections.len(),
+            seats,
+            mode,
+            nomination_end: now + nomination_period_ms,
+            voting_end,
+            term_end: voting_end + term_ms,
+            candidates: vec![],
+            winners: vec![],
+            finalized: false,
+        };
+        self.elections.push(&election);
+    }
+
+    fn internal_last_election(&self) -> Option<Election> {
+        match self.elections.len() {
+            0 => None,
+            len => self.elections.get(len - 1),
+        }
+    }
+
+    fn internal_current_election(&self) -> Election {
+        let election = self
+            .internal_last_election()
+            .unwrap_or_else(|| DaoError::NoElection.panic());
+        require!(!election.finalized, DaoError::ElectionAlreadyFinalized.to_string());
+        election
+    }
+
+    fn internal_save_election(&mut self, election: &Election) {
+        self.elections.replace(election.term_id, election);
+    }
+
+    /// Sobald eine Wahl abgeschlossen ist, wird Core nur noch über Wahlen vergeben
+    pub(crate) fn internal_council_elected(&self) -> bool {
+        self.internal_last_election()
+            .is_some_and(|election| election.finalized || election.term_id > 0)
+    }
+
+    /// Core-Rolle aus einer Wahl (mit Ablaufdatum); dauerhafte Core-Rollen (Owner, Team) zählen nicht
+    fn internal_has_elected_core(&self, account_id: &AccountId) -> bool {
+        let now = env::block_timestamp_ms();
+        self.roles
+            .get(account_id)
+            .unwrap_or_default()
+            .iter()
+            .any(|grant| grant.role == ROLE_CORE && grant.expires_at.is_some() && grant.is_active(now))
+    }
+
+    fn internal_has_permanent_core(&self, account_id: &AccountId) -> bool {
+        self.roles
+            .get(account_id)
+            .unwrap_or_default()
+            .iter()
+            .any(|grant| grant.role == ROLE_CORE && grant.expires_at.is_none())
+    }
+
+    /// Hält das Guthaben vor der ersten Änderung während einer offenen token-gewichteten Abstimmung fest,
+    /// damit Tokens nach der Stimmabgabe nicht weitergegeben und erneut eingesetzt werden können.
+    pub(crate) fn internal_snapshot_election_balance(&mut self, account_id: &AccountId, balance: NearToken) {
+        let Some(election) = self.internal_last_election() else {
+            return;
+        };
+        let now = env::block_timestamp_ms();
+        if election.finalized
+            || election.mode != VotingMode::TokenWeighted
+            || now < election.nomination_end
+            || now >= election.voting_end
+        {
+            return;
+        }
+        if self.election_snapshots.get(account_id).map(|(term_id, _)| term_id) != Some(election.term_id) {
+            self.election_snapshots.insert(account_id, &(election.term_id, balance));
+        }
+    }
+
+    /// Stimmgewicht bei TokenWeighted: Guthaben zum Beginn der Abstimmung
+    fn internal_election_weight(&self, voter: &AccountId, election: &Election) -> u128 {
+        match self.election_snapshots.get(voter) {
+            Some((term_id, balance)) if term_id == election.term_id => balance.as_yoctonear(),
+            _ => self.accounts.get(voter).unwrap_or(ZERO_TOKEN).as_yoctonear(),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Selbstnominierung während des Nominierungsfensters (nur stimmberechtigte Mitglieder)
+    pub fn nominate(&mut self) {
+        let candidate = env::predecessor_account_id();
+        self.require_permission(&candidate, Permission::Vote);
+
+        let mut election = self.internal_current_election();
+        require!(
+            env::block_timestamp_ms() < election.nomination_end,
+            DaoError::NominationClosed.to_string()
+        );
+        require!(
+            !election.candidates.iter().any(|(acc, _)| acc == &candidate),
+            DaoError::AlreadyNominated.to_string()
+        );
+
+        election.candidates.push((candidate.clone(), 0));
+        self.internal_save_election(&election);
+
+        CandidateNominated {
+            term_id: election.term_id,
+            candidate: &candidate,
+        }
+        .emit();
+    }
+
+    /// Stimmabgabe für bis zu `seats` Kandidaten
+    #[payable]
+    pub fn vote_in_election(&mut self, candidates: Vec<AccountId>) {
+        assert_one_yocto();
+        let voter = env::predecessor_account_id();
+        self.require_permission(&voter, Permission::Vote);
+
+        let mut election = self.internal_current_election();
+        let now = env::block_timestamp_ms();
+        require!(
+            now >= election.nomination_end && now < election.voting_end,
+            DaoError::VotingNotOpen.to_string()
+        );
+        require!(
+            !candidates.is_empty() && candidates.len() <= election.seats as usize,
+            DaoError::InvalidBallot.with_detail(election.seats)
+        );
+        require!(
+            self.election_voters.get(&voter) != Some(election.term_id),
+            DaoError::AlreadyVoted.with_detail(election.term_id)
+        );
+
+        let weight = match election.mode {
+            VotingMode::TokenWeighted => self.internal_election_weight(&voter, &election),
+            VotingMode::OneMemberOneVote => 1,
+        };
+        for (index, candidate) in candidates.iter().enumerate() {
+            require!(
+                !candidates[..index].contains(candidate),
+                DaoError::InvalidBallot.with_detail(candidate)
+            );
+            let entry = election
+                .candidates
+                .iter_mut()
+                .find(|(acc, _)| acc == candidate)
+                .unwrap_or_else(|| DaoError::UnknownCandidate.panic_with(candidate));
+            entry.1 += weight;
+        }
+
+        self.election_voters.insert(&voter, &election.term_id);
+        self.internal_save_election(&election);
+
+        ElectionVoted {
+            term_id: election.term_id,
+            voter: &voter,
+            candidates: &candidates,
+            weight: U128(weight),
+        }
+        .emit();
+    }
+
+    /// Schliesst die Wahl nach Ablauf der Abstimmung ab: Gewinner (mit mindestens einer Stimme) erhalten
+    /// die Core-Rolle bis `term_end`, nicht wiedergewählte Council-Mitglieder verlieren die gewählte Rolle.
+    /// Dauerhafte Core-Rollen bleiben unberührt, daher bleibt die Core-Invariante erfüllt.
+    /// Kann von jedem aufgerufen werden.
+    pub fn finalize_election(&mut self) {
+        let mut election = self.internal_current_election();
+        require!(
+            env::block_timestamp_ms() >= election.voting_end,
+            DaoError::VotingStillOpen.to_string()
+        );
+
+        // Stabile Sortierung: bei Gleichstand gewinnt die frühere Nominierung
+        let mut ranking = election.candidates.clone();
+        ranking.sort_by_key(|(_, votes)| std::cmp::Reverse(*votes));
+        election.winners = ranking
+            .into_iter()
+            .filter(|(_, votes)| *votes > 0)
+            .take(election.seats as usize)
+            .map(|(account_id, _)| account_id)
+            .collect();
+        election.finalized = true;
+        self.internal_save_election(&election);
+
+        let reason = format!("Core council election, term {}", election.term_id);
+        let now = env::block_timestamp_ms();
+        for winner in election.winners.iter() {
+            if self.accounts.get(winner).is_none() {
+                self.internal_register_account(winner);
+            }
+            if election.term_end > now && !self.internal_has_permanent_core(winner) {
+                self.internal_grant_role(winner, ROLE_CORE, Some(election.term_end), Some(reason.clone()));
+            }
+        }
+        let mut council = election.winners.clone();
+        for member in std::mem::take(&mut self.council) {
+            if election.winners.contains(&member) {
+                continue;
+            }
+            let lost = election.candidates.iter().any(|(candidate, _)| candidate == &member);
+            if election.winners.is_empty() && !lost {
+                // Ohne gewählte Kandidaten bleibt der bisherige Council im Amt (ausser abgewählte Kandidaten)
+                council.push(member);
+            } else if self.internal_has_elected_core(&member) {
+                self.internal_revoke_role(&member, ROLE_CORE, Some(reason.clone()));
+            }
+        }
+        self.council = council;
+
+        ElectionFinalized {
+            term_id: election.term_id,
+            winners: &election.winners,
+            council: &self.council,
+        }
+        .emit();
+    }
+
+    /// Aktuelle (oder zuletzt abgeschlossene) Wahl
+    pub fn get_current_election(&self) -> Option<Election> {
+        self.internal_last_election()
+    }
+
+    pub fn get_candidates(&self, term_id: u64) -> Vec<(AccountId, U128)> {
+        self.elections
+            .get(term_id)
+            .map(|election| {
+                election
+                    .candidates
+                    .into_iter()
+                    .map(|(account_id, votes)| (account_id, U128(votes)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gewinner einer abgeschlossenen Wahl
+    pub fn get_election_results(&self, term_id: u64) -> Option<Vec<AccountId>> {
+        self.elections
+            .get(term_id)
+            .filter(|election| election.finalized)
+            .map(|election| election.winners)
+    }
+
+    /// Alle bisherigen Wahlperioden (paginiert)
+    pub fn get_term_history(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<Election> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(50);
+        (from_index..std::cmp::min(from_index.saturating_add(limit), self.elections.len()))
+            .filter_map(|index| self.elections.get(index))
+            .collect()
+    }
+
+    pub fn get_council(&self) -> Vec<AccountId> {
+        self.council.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    const PERIOD_MS: u64 = 1_000;
+    const TERM_MS: u64 = 100 * PERIOD_MS;
+
+    /// Startet eine Wahl zum Zeitpunkt `start` und nominiert die Kandidaten
+    fn start_election(contract: &mut Contract, start: u64, seats: u32, candidates: &[&AccountId]) {
+        set_context(&owner(), ZERO_TOKEN, start);
+        contract.internal_start_election(seats, VotingMode::TokenWeighted, PERIOD_MS, PERIOD_MS, TERM_MS);
+        for candidate in candidates {
+            set_context(candidate, ZERO_TOKEN, start);
+            contract.nominate();
+        }
+    }
+
+    fn vote(contract: &mut Contract, voter: &AccountId, now: u64, candidates: &[&AccountId]) {
+        set_context(voter, ONE_YOCTO, now);
+        contract.vote_in_election(candidates.iter().map(|candidate| (*candidate).clone()).collect());
+    }
+
+    fn finalize(contract: &mut Contract, now: u64) {
+        set_context(&owner(), ZERO_TOKEN, now);
+        contract.finalize_election();
+    }
+
+    #[test]
+    fn token_weight_is_fixed_when_voting_opens() {
+        let mut contract = setup();
+        let (bob, carol, dave) = (account("bob"), account("carol"), account("dave"));
+        fund(&mut contract, &bob, 200);
+        fund(&mut contract, &carol, 300);
+        fund(&mut contract, &dave, 150);
+        start_election(&mut contract, START_MS, 1, &[&bob]);
+
+        let voting_start = START_MS + PERIOD_MS;
+        vote(&mut contract, &carol, voting_start, &[&bob]);
+        // Carol gibt ihre Tokens nach der Stimmabgabe an Dave weiter
+        contract.internal_transfer(&carol, &dave, NearToken::from_yoctonear(300 * ONE_TOKEN), None);
+        vote(&mut contract, &dave, voting_start + 1, &[&bob]);
+
+        assert_eq!(contract.get_candidates(0), vec![(bob, U128(450 * ONE_TOKEN))]);
+    }
+
+    #[test]
+    fn balance_changes_before_voting_count() {
+        let mut contract = setup();
+        let (bob, carol) = (account("bob"), account("carol"));
+        fund(&mut contract, &bob, 200);
+        start_election(&mut contract, START_MS, 1, &[&bob]);
+        // Während der Nominierung erworbene Tokens zählen voll
+        fund(&mut contract, &carol, 300);
+
+        vote(&mut contract, &carol, START_MS + PERIOD_MS, &[&bob]);
+        assert_eq!(contract.get_candidates(0), vec![(bob, U128(300 * ONE_TOKEN))]);
+    }
+
+    #[test]
+    fn candidates_without_votes_are_not_seated() {
+        let mut contract = setup();
+        let (bob, carol, dave) = (account("bob"), account("carol"), account("dave"));
+        fund(&mut contract, &bob, 200);
+        fund(&mut contract, &carol, 200);
+        fund(&mut contract, &dave, 200);
+        start_election(&mut contract, START_MS, 2, &[&bob, &carol]);
+        vote(&mut contract, &dave, START_MS + PERIOD_MS, &[&carol]);
+        finalize(&mut contract, START_MS + 2 * PERIOD_MS);
+
+        assert_eq!(contract.get_election_results(0), Some(vec![carol.clone()]));
+        assert_eq!(contract.get_council(), vec![carol.clone()]);
+        assert!(contract.internal_has_role(&carol, ROLE_CORE));
+        assert!(!contract.internal_has_role(&bob, ROLE_CORE));
+    }
+
+    #[test]
+    fn losing_council_members_are_demoted() {
+        let mut contract = setup();
+        let (bob, carol, dave) = (account("bob"), account("carol"), account("dave"));
+        fund(&mut contract, &bob, 200);
+        fund(&mut contract, &carol, 200);
+        fund(&mut contract, &dave, 200);
+        start_election(&mut contract, START_MS, 2, &[&bob, &carol]);
+        vote(&mut contract, &dave, START_MS + PERIOD_MS, &[&bob, &carol]);
+        finalize(&mut contract, START_MS + 2 * PERIOD_MS);
+        assert_eq!(contract.get_council(), vec![bob.clone(), carol.clone()]);
+
+        // Neue Wahl: Bob kandidiert erneut, erhält aber keine Stimme; Carol tritt nicht an
+        let second = START_MS + 10 * PERIOD_MS;
+        start_election(&mut contract, second, 2, &[&bob]);
+        finalize(&mut contract, second + 2 * PERIOD_MS);
+
+        assert_eq!(contract.get_election_results(1), Some(vec![]));
+        assert!(!contract.internal_has_role(&bob, ROLE_CORE));
+        assert!(contract.internal_has_role(&carol, ROLE_CORE));
+        assert_eq!(contract.get_council(), vec![carol]);
+    }
+
+    #[test]
+    fn elected_core_expires_at_the_end_of_the_term() {
+        let mut contract = setup();
+        let (bob, dave) = (account("bob"), account("dave"));
+        fund(&mut contract, &bob, 200);
+        fund(&mut contract, &dave, 200);
+        start_election(&mut contract, START_MS, 1, &[&bob]);
+        vote(&mut contract, &dave, START_MS + PERIOD_MS, &[&bob]);
+        finalize(&mut contract, START_MS + 2 * PERIOD_MS);
+
+        let term_end = START_MS + 2 * PERIOD_MS + TERM_MS;
+        assert_eq!(contract.get_current_election().unwrap().term_end, term_end);
+        let grants = contract.get_role_grants(bob.clone());
+        assert!(grants.iter().any(|grant| grant.role == ROLE_CORE && grant.expires_at == Some(term_end)));
+        set_context(&owner(), ZERO_TOKEN, term_end);
+        assert!(!contract.internal_has_role(&bob, ROLE_CORE));
+    }
+
+    #[test]
+    fn losing_the_last_permanent_core_keeps_the_election_finalizable() {
+        let mut contract = setup();
+        let dave = account("dave");
+        fund(&mut contract, &dave, 200);
+        // Owner ist das einzige dauerhafte Core-Mitglied und gewinnt die erste Wahl
+        contract.internal_revoke_role(&team(), ROLE_CORE, None);
+        start_election(&mut contract, START_MS, 1, &[&owner()]);
+        vote(&mut contract, &dave, START_MS + PERIOD_MS, &[&owner()]);
+        finalize(&mut contract, START_MS + 2 * PERIOD_MS);
+        assert_eq!(contract.get_council(), vec![owner()]);
+
+        // Zweite Wahl ohne Stimmen: der Owner verliert den Sitz, nicht aber seine dauerhafte Core-Rolle
+        let second = START_MS + 10 * PERIOD_MS;
+        start_election(&mut contract, second, 1, &[&owner()]);
+        finalize(&mut contract, second + 2 * PERIOD_MS);
+
+        assert!(contract.get_current_election().unwrap().finalized);
+        assert!(contract.get_council().is_empty());
+        assert!(contract.internal_has_role(&owner(), ROLE_CORE));
+    }
+
+    #[test]
+    #[should_panic(expected = "CoreRoleIsElected")]
+    fn core_cannot_be_assigned_directly_after_an_election() {
+        let mut contract = setup();
+        start_election(&mut contract, START_MS, 1, &[]);
+        finalize(&mut contract, START_MS + 2 * PERIOD_MS);
+
+        set_context(&owner(), ONE_YOCTO, START_MS + 3 * PERIOD_MS);
+        contract.assign_role(account("bob"), ROLE_CORE.to_string(), None, None);
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt;
+
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::{self, json};
+
+use crate::*;
+
+/// Erzeugt aus einer Tabelle `Variante = Code => Beschreibung` das Enum, `ALL` und `description()`,
+/// damit keine der drei Stellen beim Ergänzen vergessen werden kann.
+macro_rules! dao_errors {
+    (@one $name:ident) => {
+        1
+    };
+    ($($name:ident = $code:literal => $description:literal,)*) => {
+        /// Fehler des Contracts mit stabilen Codes (nie umnummerieren, nur ergänzen).
+        /// 1xxx Accounts/Token, 2xxx Initialisierung/Verkauf/Konfiguration, 3xxx Governance,
+        /// 4xxx Rollen (41xx Mitgliedsanträge, 42xx Wahlen), 5xxx Dividenden,
+        /// 6xxx Treasury (61xx Budgets, 62xx Allowances), 7xxx Streams (71xx Grants, 72xx Bounties),
+        /// 8xxx Betrieb: Pause (81xx Multisig, 82xx Upgrades, 83xx State-Migration).
+        ///
+        /// Panics haben das Format
+        /// `{"code":1001,"error":"AccountNotRegistered","message":"...","detail":...}`,
+        /// damit Frontend und Bots sie ohne Textvergleich zuordnen können.
+        #[derive(Serialize, Deserialize, NearSchema, PartialEq, Eq, Debug, Clone, Copy)]
+        #[serde(crate = "near_sdk::serde")]
+        #[repr(u32)]
+        pub enum DaoError {
+            $($name = $code,)*
+        }
+
+        impl DaoError {
+            pub const ALL: [DaoError; 0 $(+ dao_errors!(@one $name))*] = [$(DaoError::$name,)*];
+
+            pub fn description(self) -> &'static str {
+                match self {
+                    $(DaoError::$name => $description,)*
+                }
+            }
+        }
+    };
+}
+
+dao_errors! {
+    AccountNotRegistered = 1001 => "The account is not registered",
+    AccountAlreadyRegistered = 1002 => "The account is already registered",
+    InsufficientBalance = 1003 => "The account doesn't have enough balance",
+    BalanceOverflow = 1004 => "Balance overflow",
+    SameSenderAndReceiver = 1005 => "Sender and receiver should be different",
+    NonPositiveAmount = 1006 => "The amount should be a positive number",
+    TotalSupplyOverflow = 1007 => "Total supply overflow",
+    InsufficientStorageDeposit = 1008 => "The attached deposit is less than the minimum storage balance",
+    TreasuryEqualsTeam = 2001 => "Treasury and team account must be different",
+    ZeroDeposit = 2002 => "You must send a positive amount of NEAR",
+    InvalidDaoAccount = 2003 => "Invalid treasury or team account",
+    DaoAccountNotEmpty = 2004 => "The new treasury or team account already holds tokens",
+    InvalidSaleRate = 2005 => "The sale rate must be positive",
+    VotingPeriodTooShort = 2006 => "The voting period is too short",
+    QuorumExceedsSupply = 2007 => "The quorum exceeds the total supply",
+    InvalidMultisigThreshold = 2008 => "The multisig threshold must be at least 1",
+    MultisigThresholdExceedsSigners = 2009 => "The multisig threshold exceeds the number of signers",
+    InvalidMultisigSigner = 2010 => "Multisig signers must be core or finance members",
+    InvalidApprovalTtl = 2011 => "The approval TTL must be positive",
+    InvalidPauseQuorum = 2012 => "The pause quorum must be at least 1",
+    ProposalNotFound = 3001 => "Proposal not found",
+    ProposalAlreadyFinalized = 3002 => "Proposal is already finalized",
+    VotingDeadlinePassed = 3003 => "The voting deadline has passed",
+    AlreadyVoted = 3004 => "You have already voted",
+    InsufficientProposalBond = 3005 => "The attached deposit is less than the proposal bond",
+    ProposalNotAccepted = 3006 => "Proposal not accepted",
+    ProposalAlreadyExecuted = 3007 => "Proposal already executed",
+    MissingTransferAmount = 3008 => "No amount specified",
+    MissingTransferTarget = 3009 => "No target specified",
+    InvalidTargetAccount = 3010 => "Invalid target account",
+    InsufficientTreasury = 3011 => "Not enough in treasury account",
+    MissingPermission = 4001 => "The account lacks the required permission",
+    InvalidRole = 4002 => "Invalid role",
+    RoleExpiryInPast = 4003 => "Role expiry must be in the future",
+    InvalidThresholdRole = 4004 => "Invalid threshold role",
+    VisitorRoleImmutable = 4005 => "The visitor role cannot be revoked or removed",
+    RoleNotHeld = 4006 => "The account does not hold this role",
+    PermanentCoreRequired = 4007 => "At least one permanent core member is required",
+    RoleManagerRequired = 4008 => "At least one role must keep the ManageRoles permission",
+    CoreRoleIsElected = 4009 => "The core role is assigned by council elections",
+    ApplicationNotFound = 4101 => "Application not found",
+    ApplicationAlreadyDecided = 4102 => "Application is already decided",
+    RoleAlreadyHeld = 4103 => "You already hold this role",
+    ApplicationPending = 4104 => "You already have a pending application",
+    InvalidElectionSeats = 4201 => "An election needs at least one seat",
+    InvalidElectionPeriods = 4202 => "Election periods must be positive",
+    ElectionNotFinalized = 4203 => "The current election is not finalized yet",
+    NoElection = 4204 => "No election has been started",
+    ElectionAlreadyFinalized = 4205 => "The election is already finalized",
+    NominationClosed = 4206 => "The nomination window is closed",
+    AlreadyNominated = 4207 => "You are already nominated",
+    VotingNotOpen = 4208 => "Voting is not open",
+    InvalidBallot = 4209 => "Vote for at least one and at most `seats` distinct candidates",
+    UnknownCandidate = 4210 => "Unknown candidate",
+    VotingStillOpen = 4211 => "Voting is still open",
+    NoCirculatingSupply = 5001 => "No circulating supply",
+    TreasuryBalanceOverflow = 6001 => "Treasury balance overflow",
+    InsufficientTreasuryFunds = 6002 => "Not enough funds in treasury",
+    StorageReserveViolation = 6003 => "Not enough NEAR in treasury after storage reserve",
+    DaoTokenDeposit = 6004 => "DAO tokens cannot be deposited into the treasury ledger",
+    EmptyBudgetCategory = 6101 => "Budget category must not be empty",
+    InvalidBudgetPeriod = 6102 => "Budget period must be positive",
+    BudgetNotFound = 6103 => "Budget not found",
+    BudgetExceeded = 6104 => "Budget exceeded",
+    FinanceRoleRequired = 6201 => "Allowances are only available to finance members",
+    InvalidAllowancePeriod = 6202 => "Allowance period must be positive",
+    InvalidAllowanceLimits = 6203 => "per_tx_max must be positive and not exceed per_period_max",
+    AllowanceNotFound = 6204 => "Allowance not found",
+    TransactionLimitExceeded = 6205 => "Amount exceeds the per-transaction limit",
+    AllowanceExceeded = 6206 => "Amount exceeds the remaining allowance for this period",
+    RecipientNotAllowed = 6207 => "Recipient is not allowed",
+    StreamNotFound = 7001 => "Stream not found",
+    InvalidStreamRate = 7002 => "rate_per_sec must be positive",
+    InvalidStreamSchedule = 7003 => "The stream must start before its end and the cliff must lie within it",
+    StreamNotActive = 7004 => "Stream is not active",
+    NotStreamRecipient = 7005 => "Only the recipient can withdraw",
+    NothingToWithdraw = 7006 => "Nothing to withdraw",
+    StreamTotalOverflow = 7007 => "Stream total exceeds the maximum amount",
+    GrantNotFound = 7101 => "Grant not found",
+    GrantWithoutMilestones = 7102 => "A grant needs at least one milestone",
+    InvalidMilestoneAmount = 7103 => "Milestone amounts must be positive",
+    GrantCancelled = 7104 => "Grant is cancelled",
+    MilestoneNotFound = 7105 => "Milestone not found",
+    MilestoneNotSubmitted = 7106 => "Milestone has not been submitted",
+    NotGrantee = 7107 => "Only the grantee can submit milestones",
+    MilestoneAlreadyReleased = 7108 => "Milestone has already been released",
+    BountyNotFound = 7201 => "Bounty not found",
+    InvalidBountyAmount = 7202 => "Bounty amount must be positive",
+    InvalidBountyClaims = 7203 => "max_claims must be positive",
+    InvalidClaimPeriod = 7204 => "Claim period must be positive",
+    InvalidBountyDeadline = 7205 => "Bounty deadline must be in the future",
+    BountyCancelled = 7206 => "Bounty is cancelled",
+    ClaimNotFound = 7207 => "No matching claim for this account",
+    MembersOnly = 7208 => "Only members can claim bounties",
+    BountyNotOpen = 7209 => "Bounty is not open for claims",
+    AlreadyClaimed = 7210 => "You have already claimed this bounty",
+    ClaimAlreadySubmitted = 7211 => "Claim has already been submitted",
+    BountyTotalOverflow = 7212 => "Bounty amount times max_claims exceeds the maximum amount",
+    SubsystemPaused = 8001 => "This part of the DAO is paused",
+    PendingActionNotFound = 8101 => "Pending action not found",
+    NotMultisigSigner = 8102 => "Account is not a multisig signer",
+    AlreadyApproved = 8103 => "You have already approved this action",
+    NotActionCreator = 8104 => "Only the creator can cancel this action",
+    UpgradeCodeNotStaged = 8201 => "Upgrade code has not been staged",
+    UpgradeInProgress = 8202 => "Another upgrade is being deployed",
+    MissingUpgradeCode = 8203 => "Missing upgrade code",
+    NoUpgradeScheduled = 8204 => "No upgrade is scheduled",
+    UpgradeTimelockActive = 8205 => "Upgrade timelock has not expired",
+    InvalidStateVersion = 8301 => "Unknown or invalid state version",
+    StateMissing = 8302 => "Contract state is missing",
+}
+
+impl DaoError {
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|error| error.code() == code)
+    }
+
+    /// Panic-Nachricht mit Kontext (z.B. Account-ID oder fehlende Permission)
+    pub fn with_detail(self, detail: impl fmt::Display) -> String {
+        self.to_json(Some(detail.to_string()))
+    }
+
+    pub fn panic(self) -> ! {
+        env::panic_str(&self.to_string())
+    }
+
+    pub fn panic_with(self, detail: impl fmt::Display) -> ! {
+        env::panic_str(&self.with_detail(detail))
+    }
+
+    /// Liest den Fehler aus einer Panic-Nachricht (z.B. "Smart contract panicked: {...}"),
+    /// auch wenn sie escaped in einer Fehlermeldung des RPC eingebettet ist
+    pub fn from_panic_message(message: &str) -> Option<Self> {
+        let message = message.replace("\\\"", "\"");
+        let start = message.find("{\"code\"")?;
+        let payload = serde_json::Deserializer::from_str(&message[start..])
+            .into_iter::<serde_json::Value>()
+            .next()?
+            .ok()?;
+        Self::from_code(u32::try_from(payload.get("code")?.as_u64()?).ok()?)
+    }
+
+    fn to_json(self, detail: Option<String>) -> String {
+        json!({
+            "code": self.code(),
+            "error": self,
+            "message": self.description(),
+            "detail": detail,
+        })
+        .to_string()
+    }
+}
+
+impl fmt::Display for DaoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_json(None))
+    }
+}
+
+#[derive(Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ErrorCodeInfo {
+    pub code: u32,
+    pub error: DaoError,
+    pub description: String,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Alle Fehlercodes mit Beschreibung (für Frontend und Bots)
+    pub fn get_error_codes(&self) -> Vec<ErrorCodeInfo> {
+        DaoError::ALL
+            .into_iter()
+            .map(|error| ErrorCodeInfo {
+                code: error.code(),
+                error,
+                description: error.description().to_string(),
+            })
+            .collect()
+    }
+
+    pub fn get_error_description(&self, code: u32) -> Option<String> {
+        DaoError::from_code(code).map(|error| error.description().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique_and_resolvable() {
+        for error in DaoError::ALL {
+            assert_eq!(DaoError::from_code(error.code()), Some(error));
+            assert_eq!(DaoError::from_panic_message(&error.with_detail(1)), Some(error));
+        }
+    }
+}
@@ -1,12 +1,27 @@
 use near_sdk::{env, NearToken, AccountId};
+use near_sdk::json_types::{Base58CryptoHash, U128};
 use near_sdk::serde::Serialize;
 
+use crate::allowances::FinanceSpendRecord;
+use crate::bounties::Bounty;
+use crate::config::ConfigUpdate;
+use crate::grants::Grant;
+use crate::membership::ApplicationStatus;
+use crate::multisig::SensitiveAction;
+use crate::pause::Subsystem;
+use crate::proposal::{ProposalKind, ProposalStatus};
+use crate::roles::RoleChange;
+use crate::streams::PaymentStream;
+use crate::treasury::Asset;
+use crate::upgrade::UpgradeStatus;
+
 #[derive(Serialize, Debug)]
 #[serde(tag = "standard")]
 #[must_use = "don't forget to `.emit()` this event"]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum NearEvent<'a> {
     Nep141(Nep141Event<'a>),
+    ThesisDao(DaoEvent<'a>),
 }
 
 impl<'a> NearEvent<'a> {
@@ -84,3 +99,552 @@ fn new_141<'a>(version: &'static str, event_kind: Nep141EventKind<'a>) -> NearEv
 fn new_141_v1(event_kind: Nep141EventKind) -> NearEvent {
     new_141("1.0.0", event_kind)
 }
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ConfigUpdated<'a> {
+    pub version: u32,
+    pub update: &'a ConfigUpdate,
+}
+
+impl ConfigUpdated<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ConfigUpdated(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceSpend<'a> {
+    #[serde(flatten)]
+    pub record: &'a FinanceSpendRecord,
+}
+
+impl FinanceSpend<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::FinanceSpend(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ProposalCreated<'a> {
+    pub proposal_id: u64,
+    pub proposer: &'a AccountId,
+    pub title: &'a str,
+    pub kind: &'a ProposalKind,
+    pub deadline: Option<u64>,
+}
+
+impl ProposalCreated<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ProposalCreated(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ProposalVoted<'a> {
+    pub proposal_id: u64,
+    pub voter: &'a AccountId,
+    pub support: bool,
+    pub weight: U128,
+}
+
+impl ProposalVoted<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ProposalVoted(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ProposalFinalized<'a> {
+    pub proposal_id: u64,
+    pub status: &'a ProposalStatus,
+    pub votes_for: U128,
+    pub votes_against: U128,
+    /// `false`, wenn der Bond mangels Quorum an die Treasury verfallen ist
+    pub bond_refunded: bool,
+}
+
+impl ProposalFinalized<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ProposalFinalized(&[self])).emit()
+    }
+}
+
+/// Bei Treasury-Auszahlungen erst nach erfolgreichem Abschluss (aus dem Callback), siehe ProposalPayoutResolved
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ProposalExecuted<'a> {
+    pub proposal_id: u64,
+    pub kind: &'a ProposalKind,
+}
+
+impl ProposalExecuted<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ProposalExecuted(&[self])).emit()
+    }
+}
+
+/// Ergebnis einer asynchronen Treasury-Auszahlung; `status` ist der endgültige Proposal-Status (Executed/Failed)
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ProposalPayoutResolved<'a> {
+    pub proposal_id: u64,
+    pub asset: &'a Asset,
+    pub amount: U128,
+    pub status: &'a ProposalStatus,
+}
+
+impl ProposalPayoutResolved<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ProposalPayoutResolved(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct RoleChanged<'a> {
+    #[serde(flatten)]
+    pub change: &'a RoleChange,
+}
+
+impl RoleChanged<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::RoleChanged(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct TeamChanged<'a> {
+    pub account_id: &'a AccountId,
+    pub added: bool,
+}
+
+impl TeamChanged<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::TeamChanged(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct TokensPurchased<'a> {
+    pub buyer: &'a AccountId,
+    pub near_amount: U128,
+    pub token_amount: U128,
+}
+
+impl TokensPurchased<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::TokensPurchased(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct DividendsDistributed {
+    pub total_amount: U128,
+    pub total_supply: U128,
+    pub recipients: u32,
+}
+
+impl DividendsDistributed {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::DividendsDistributed(&[self])).emit()
+    }
+}
+
+/// Freigabe einer Multisig-Aktion; `executed` ist true, sobald M Freigaben erreicht sind
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ActionApproved<'a> {
+    pub action_id: u64,
+    pub action: &'a SensitiveAction,
+    pub signer: &'a AccountId,
+    pub approvals: u32,
+    pub executed: bool,
+}
+
+impl ActionApproved<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ActionApproved(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct PauseChanged<'a> {
+    pub subsystem: Subsystem,
+    pub paused: bool,
+    pub actor: &'a AccountId,
+}
+
+impl PauseChanged<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::PauseChanged(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ApplicationSubmitted<'a> {
+    pub application_id: u64,
+    pub applicant: &'a AccountId,
+    pub requested_role: &'a str,
+}
+
+impl ApplicationSubmitted<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ApplicationSubmitted(&[self])).emit()
+    }
+}
+
+/// Entscheidung über einen Mitgliedsantrag (Approved oder Rejected)
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ApplicationDecided<'a> {
+    pub application_id: u64,
+    pub applicant: &'a AccountId,
+    pub status: &'a ApplicationStatus,
+    pub decided_by: &'a AccountId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'a str>,
+}
+
+impl ApplicationDecided<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ApplicationDecided(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct CandidateNominated<'a> {
+    pub term_id: u64,
+    pub candidate: &'a AccountId,
+}
+
+impl CandidateNominated<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::CandidateNominated(&[self])).emit()
+    }
+}
+
+/// Stimmzettel einer Wahl; `weight` wird jedem gewählten Kandidaten gutgeschrieben
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ElectionVoted<'a> {
+    pub term_id: u64,
+    pub voter: &'a AccountId,
+    pub candidates: &'a [AccountId],
+    pub weight: U128,
+}
+
+impl ElectionVoted<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ElectionVoted(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ElectionFinalized<'a> {
+    pub term_id: u64,
+    pub winners: &'a [AccountId],
+    pub council: &'a [AccountId],
+}
+
+impl ElectionFinalized<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ElectionFinalized(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct StreamCreated<'a> {
+    #[serde(flatten)]
+    pub stream: &'a PaymentStream,
+}
+
+impl StreamCreated<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::StreamCreated(&[self])).emit()
+    }
+}
+
+/// Abgeschlossene Auszahlung aus einem Stream (bei NEAR/NEP-141 erst aus dem Callback)
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct StreamWithdrawn<'a> {
+    pub stream_id: u64,
+    pub recipient: &'a AccountId,
+    pub amount: U128,
+}
+
+impl StreamWithdrawn<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::StreamWithdrawn(&[self])).emit()
+    }
+}
+
+/// `refunded` ist der an die Treasury zurückgebuchte, noch nicht freigegebene Rest
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct StreamCancelled {
+    pub stream_id: u64,
+    pub cancelled_at: u64,
+    pub refunded: U128,
+}
+
+impl StreamCancelled {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::StreamCancelled(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct GrantCreated<'a> {
+    #[serde(flatten)]
+    pub grant: &'a Grant,
+}
+
+impl GrantCreated<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::GrantCreated(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct MilestoneSubmitted<'a> {
+    pub grant_id: u64,
+    pub milestone_index: u32,
+    pub evidence_link: &'a str,
+    pub evidence_hash: &'a str,
+}
+
+impl MilestoneSubmitted<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::MilestoneSubmitted(&[self])).emit()
+    }
+}
+
+/// Ausgezahlte Tranche (bei NEAR/NEP-141 erst aus dem Callback)
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct MilestoneReleased<'a> {
+    pub grant_id: u64,
+    pub milestone_index: u32,
+    pub grantee: &'a AccountId,
+    pub amount: U128,
+}
+
+impl MilestoneReleased<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::MilestoneReleased(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct GrantCancelled {
+    pub grant_id: u64,
+    pub refunded: U128,
+}
+
+impl GrantCancelled {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::GrantCancelled(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct BountyCreated<'a> {
+    #[serde(flatten)]
+    pub bounty: &'a Bounty,
+}
+
+impl BountyCreated<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::BountyCreated(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct BountyClaimed<'a> {
+    pub bounty_id: u64,
+    pub account_id: &'a AccountId,
+    pub expires_at: u64,
+}
+
+impl BountyClaimed<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::BountyClaimed(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct BountySubmitted<'a> {
+    pub bounty_id: u64,
+    pub account_id: &'a AccountId,
+    pub proof: &'a str,
+}
+
+impl BountySubmitted<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::BountySubmitted(&[self])).emit()
+    }
+}
+
+/// Entscheidung über eine Einreichung. Genehmigungen werden erst nach der Auszahlung gemeldet
+/// (bei NEAR/NEP-141 aus dem Callback), `amount` ist dann der ausgezahlte Betrag.
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct BountyClaimReviewed<'a> {
+    pub bounty_id: u64,
+    pub claimant: &'a AccountId,
+    pub approved: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<U128>,
+}
+
+impl BountyClaimReviewed<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::BountyClaimReviewed(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct BountyCancelled {
+    pub bounty_id: u64,
+    pub refunded: U128,
+}
+
+impl BountyCancelled {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::BountyCancelled(&[self])).emit()
+    }
+}
+
+/// Eingang in den Treasury-Ledger (Spende in NEAR oder NEP-141 Token)
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct TreasuryDeposit<'a> {
+    pub asset: &'a Asset,
+    pub sender: &'a AccountId,
+    pub amount: U128,
+}
+
+impl TreasuryDeposit<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::TreasuryDeposit(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct ActionCancelled<'a> {
+    pub action_id: u64,
+    pub action: &'a SensitiveAction,
+    pub actor: &'a AccountId,
+}
+
+impl ActionCancelled<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::ActionCancelled(&[self])).emit()
+    }
+}
+
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct UpgradeCodeStaged<'a> {
+    pub code_hash: &'a Base58CryptoHash,
+    pub staged_by: &'a AccountId,
+    pub storage_cost: U128,
+}
+
+impl UpgradeCodeStaged<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::UpgradeCodeStaged(&[self])).emit()
+    }
+}
+
+/// Statuswechsel eines beschlossenen Upgrades (Scheduled, Deploying, Deployed, Failed)
+#[must_use]
+#[derive(Serialize, Debug, Clone)]
+pub struct UpgradeStatusChanged<'a> {
+    pub proposal_id: u64,
+    pub code_hash: &'a Base58CryptoHash,
+    pub status: &'a UpgradeStatus,
+}
+
+impl UpgradeStatusChanged<'_> {
+    pub fn emit(self) {
+        new_dao_v1(DaoEventKind::UpgradeStatusChanged(&[self])).emit()
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct DaoEvent<'a> {
+    version: &'static str,
+    #[serde(flatten)]
+    event_kind: DaoEventKind<'a>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+enum DaoEventKind<'a> {
+    ConfigUpdated(&'a [ConfigUpdated<'a>]),
+    FinanceSpend(&'a [FinanceSpend<'a>]),
+    ProposalCreated(&'a [ProposalCreated<'a>]),
+    ProposalVoted(&'a [ProposalVoted<'a>]),
+    ProposalFinalized(&'a [ProposalFinalized<'a>]),
+    ProposalExecuted(&'a [ProposalExecuted<'a>]),
+    ProposalPayoutResolved(&'a [ProposalPayoutResolved<'a>]),
+    RoleChanged(&'a [RoleChanged<'a>]),
+    TeamChanged(&'a [TeamChanged<'a>]),
+    TokensPurchased(&'a [TokensPurchased<'a>]),
+    DividendsDistributed(&'a [DividendsDistributed]),
+    ActionApproved(&'a [ActionApproved<'a>]),
+    PauseChanged(&'a [PauseChanged<'a>]),
+    ApplicationSubmitted(&'a [ApplicationSubmitted<'a>]),
+    ApplicationDecided(&'a [ApplicationDecided<'a>]),
+    CandidateNominated(&'a [CandidateNominated<'a>]),
+    ElectionVoted(&'a [ElectionVoted<'a>]),
+    ElectionFinalized(&'a [ElectionFinalized<'a>]),
+    StreamCreated(&'a [StreamCreated<'a>]),
+    StreamWithdrawn(&'a [StreamWithdrawn<'a>]),
+    StreamCancelled(&'a [StreamCancelled]),
+    GrantCreated(&'a [GrantCreated<'a>]),
+    MilestoneSubmitted(&'a [MilestoneSubmitted<'a>]),
+    MilestoneReleased(&'a [MilestoneReleased<'a>]),
+    GrantCancelled(&'a [GrantCancelled]),
+    BountyCreated(&'a [BountyCreated<'a>]),
+    BountyClaimed(&'a [BountyClaimed<'a>]),
+    BountySubmitted(&'a [BountySubmitted<'a>]),
+    BountyClaimReviewed(&'a [BountyClaimReviewed<'a>]),
+    BountyCancelled(&'a [BountyCancelled]),
+    TreasuryDeposit(&'a [TreasuryDeposit<'a>]),
+    ActionCancelled(&'a [ActionCancelled<'a>]),
+    UpgradeCodeStaged(&'a [UpgradeCodeStaged<'a>]),
+    UpgradeStatusChanged(&'a [UpgradeStatusChanged<'a>]),
+}
+
+fn new_dao<'a>(version: &'static str, event_kind: DaoEventKind<'a>) -> NearEvent<'a> {
+    NearEvent::ThesisDao(DaoEvent { version, event_kind })
+}
+
+fn new_dao_v1(event_kind: DaoEventKind) -> NearEvent {
+    new_dao("1.0.0", event_kind)
+}
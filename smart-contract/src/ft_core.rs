@@ -1,8 +1,10 @@
-use near_sdk::{Gas, ext_contract, PromiseOrValue, assert_one_yocto, PromiseResult};
+use near_sdk::{Gas, ext_contract, PromiseOrValue, assert_one_yocto};
 use crate::*;
 
 const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas::from_tgas(5);
 const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas::from_tgas(25).saturating_add(GAS_FOR_RESOLVE_TRANSFER);
+// Rückgabe von ft_on_transfer: JSON-String mit maximal 39 Ziffern
+const MAX_RESOLVE_RESULT_LEN: usize = 64;
 
 #[ext_contract(ext_ft_core)]
 pub trait FungibleTokenCore {
@@ -23,6 +25,7 @@ impl FungibleTokenCore for Contract {
     #[payable]
     fn ft_transfer(&mut self, receiver_id: AccountId, amount: NearToken, memo: Option<String>) {
         assert_one_yocto();
+        self.assert_not_paused(Subsystem::Transfers);
         let sender_id = env::predecessor_account_id();
         self.internal_transfer(&sender_id, &receiver_id, amount, memo);
     }
@@ -36,12 +39,13 @@ impl FungibleTokenCore for Contract {
         msg: String,
     ) -> PromiseOrValue<NearToken> {
         assert_one_yocto();
+        self.assert_not_paused(Subsystem::Transfers);
         let sender_id = env::predecessor_account_id();
         self.internal_transfer(&sender_id, &receiver_id, amount, memo);
 
         ext_ft_receiver::ext(receiver_id.clone())
             .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
-            .ft_on_transfer(sender_id.clone(), amount.into(), msg)
+            .ft_on_transfer(sender_id.clone(), amount, msg)
             .then(
                 Self::ext(env::current_account_id())
                     .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
@@ -54,7 +58,11 @@ impl FungibleTokenCore for Contract {
         U128(self.total_supply.as_yoctonear())
     }
 
+    /// Für den Contract-Account selbst: die im Escrow reservierten Tokens
     fn ft_balance_of(&self, account_id: AccountId) -> NearToken {
+        if account_id == env::current_account_id() {
+            return self.escrow_tokens;
+        }
         self.accounts.get(&account_id).unwrap_or(ZERO_TOKEN)
     }
 }
@@ -78,25 +86,25 @@ impl Contract {
         receiver_id: AccountId,
         amount: NearToken,
     ) -> NearToken {
-        let unused_amount = match env::promise_result(0) {
-            PromiseResult::Successful(value) => {
+        let unused_amount = match env::promise_result_checked(0, MAX_RESOLVE_RESULT_LEN) {
+            Ok(value) => {
                 if let Ok(unused_amount) = near_sdk::serde_json::from_slice::<NearToken>(&value) {
                     std::cmp::min(amount, unused_amount)
                 } else {
                     amount
                 }
             }
-            PromiseResult::Failed => amount,
+            Err(_) => amount,
         };
 
         if unused_amount.gt(&ZERO_TOKEN) {
             let receiver_balance = self.accounts.get(&receiver_id).unwrap_or(ZERO_TOKEN);
             if receiver_balance.gt(&ZERO_TOKEN) {
                 let refund_amount = std::cmp::min(receiver_balance, unused_amount);
-                self.internal_transfer(&receiver_id, &sender_id, refund_amount, Some("Refund".to_string()));
+                self.internal_transfer(&receiver_id, sender_id, refund_amount, Some("Refund".to_string()));
                 let used_amount = amount
                     .checked_sub(refund_amount)
-                    .unwrap_or_else(|| env::panic_str("Total supply overflow"));
+                    .unwrap_or_else(|| DaoError::TotalSupplyOverflow.panic());
                 return used_amount;
             }
         }
@@ -0,0 +1,379 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::Gas;
+use schemars::JsonSchema;
+
+use crate::treasury::{is_promise_success, Asset};
+use crate::*;
+
+const GAS_FOR_GRANT_CALLBACK: Gas = Gas::from_tgas(10);
+
+/// Meilenstein-Angabe im CreateGrant-Proposal
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, PartialEq, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[schemars(crate = "schemars")]
+pub struct MilestoneSpec {
+    pub description: String,
+    #[schemars(with = "String")]
+    pub amount: U128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, PartialEq, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum MilestoneStatus {
+    Pending,
+    /// Vom Grantee als erledigt gemeldet, wartet auf Freigabe
+    Submitted,
+    Released,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Milestone {
+    pub description: String,
+    pub amount: U128,
+    pub status: MilestoneStatus,
+    pub evidence_link: Option<String>,
+    pub evidence_hash: Option<String>,
+    pub submitted_at: Option<u64>,
+    pub released_at: Option<u64>,
+}
+
+/// Förderung mit Auszahlung in Tranchen. Der Gesamtbetrag wird beim Erstellen aus der
+/// Treasury reserviert; Zeitangaben in Sekunden.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct Grant {
+    pub id: u64,
+    pub grantee: AccountId,
+    pub asset: Asset,
+    pub milestones: Vec<Milestone>,
+    pub created_at: u64,
+    pub cancelled: bool,
+}
+
+impl Grant {
+    /// Noch nicht freigegebener (reservierter) Betrag
+    pub fn unreleased(&self) -> u128 {
+        self.milestones
+            .iter()
+            .filter(|milestone| milestone.status != MilestoneStatus::Released)
+            .map(|milestone| milestone.amount.0)
+            .sum()
+    }
+}
+
+fn now_sec() -> u64 {
+    env::block_timestamp_ms() / 1000
+}
+
+impl Contract {
+    pub(crate) fn internal_create_grant(&mut self, grantee: AccountId, asset: Asset, milestones: Vec<MilestoneSpec>) {
+        require!(!milestones.is_empty(), DaoError::GrantWithoutMilestones.to_string());
+        require!(
+            milestones.iter().all(|milestone| milestone.amount.0 > 0),
+            DaoError::InvalidMilestoneAmount.to_string()
+        );
+
+        let id = self.next_grant_id;
+        let grant = Grant {
+            id,
+            grantee,
+            asset,
+            milestones: milestones
+                .into_iter()
+                .map(|spec| Milestone {
+                    description: spec.description,
+                    amount: spec.amount,
+                    status: MilestoneStatus::Pending,
+                    evidence_link: None,
+                    evidence_hash: None,
+                    submitted_at: None,
+                    released_at: None,
+                })
+                .collect(),
+            created_at: now_sec(),
+            cancelled: false,
+        };
+
+        // Gesamtbetrag aller Tranchen reservieren
+        self.internal_reserve_from_treasury(&grant.asset, grant.unreleased(), format!("Grant #{} funding", id));
+
+        self.grants.insert(&id, &grant);
+        self.next_grant_id += 1;
+        GrantCreated { grant: &grant }.emit();
+    }
+
+    /// Gibt eine eingereichte Tranche frei und zahlt sie an den Grantee aus
+    pub(crate) fn internal_release_milestone(&mut self, grant_id: u64, milestone_index: u32) {
+        let mut grant = self
+            .grants
+            .get(&grant_id)
+            .unwrap_or_else(|| DaoError::GrantNotFound.panic_with(grant_id));
+        require!(!grant.cancelled, DaoError::GrantCancelled.with_detail(grant_id));
+        let milestone = grant
+            .milestones
+            .get_mut(milestone_index as usize)
+            .unwrap_or_else(|| DaoError::MilestoneNotFound.panic_with(milestone_index));
+        require!(
+            milestone.status == MilestoneStatus::Submitted,
+            DaoError::MilestoneNotSubmitted.to_string()
+        );
+        milestone.status = MilestoneStatus::Released;
+        milestone.released_at = Some(now_sec());
+        let amount = milestone.amount.0;
+        self.grants.insert(&grant_id, &grant);
+
+        let memo = format!("Grant #{} milestone {}", grant_id, milestone_index);
+        if let Some(transfer) = self.internal_pay_reserved(&grant.asset, &grant.grantee, amount, memo) {
+            transfer
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_GRANT_CALLBACK)
+                        .on_grant_payout(grant_id, milestone_index),
+                )
+                .detach();
+        } else {
+            MilestoneReleased {
+                grant_id,
+                milestone_index,
+                grantee: &grant.grantee,
+                amount: U128(amount),
+            }
+            .emit();
+        }
+    }
+
+    /// Bricht einen Grant ab; alle nicht freigegebenen Tranchen gehen an die Treasury zurück
+    pub(crate) fn internal_cancel_grant(&mut self, grant_id: u64) {
+        let mut grant = self
+            .grants
+            .get(&grant_id)
+            .unwrap_or_else(|| DaoError::GrantNotFound.panic_with(grant_id));
+        require!(!grant.cancelled, DaoError::GrantCancelled.with_detail(grant_id));
+        grant.cancelled = true;
+        let remainder = grant.unreleased();
+        if remainder > 0 {
+            self.internal_return_to_treasury(&grant.asset, remainder, format!("Grant #{} cancelled", grant_id));
+        }
+        self.grants.insert(&grant_id, &grant);
+        GrantCancelled {
+            grant_id,
+            refunded: U128(remainder),
+        }
+        .emit();
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Grantee meldet einen Meilenstein mit Nachweis (Link und Hash) als erledigt
+    pub fn submit_milestone(
+        &mut self,
+        grant_id: u64,
+        milestone_index: u32,
+        evidence_link: String,
+        evidence_hash: String,
+    ) {
+        let mut grant = self
+            .grants
+            .get(&grant_id)
+            .unwrap_or_else(|| DaoError::GrantNotFound.panic_with(grant_id));
+        require!(
+            env::predecessor_account_id() == grant.grantee,
+            DaoError::NotGrantee.to_string()
+        );
+        require!(!grant.cancelled, DaoError::GrantCancelled.with_detail(grant_id));
+        let milestone = grant
+            .milestones
+            .get_mut(milestone_index as usize)
+            .unwrap_or_else(|| DaoError::MilestoneNotFound.panic_with(milestone_index));
+        require!(
+            milestone.status != MilestoneStatus::Released,
+            DaoError::MilestoneAlreadyReleased.to_string()
+        );
+        MilestoneSubmitted {
+            grant_id,
+            milestone_index,
+            evidence_link: &evidence_link,
+            evidence_hash: &evidence_hash,
+        }
+        .emit();
+        milestone.status = MilestoneStatus::Submitted;
+        milestone.evidence_link = Some(evidence_link);
+        milestone.evidence_hash = Some(evidence_hash);
+        milestone.submitted_at = Some(now_sec());
+        self.grants.insert(&grant_id, &grant);
+    }
+
+    /// Freigabe einer Tranche durch einen Reviewer (alternativ per ReleaseMilestone-Proposal)
+    pub fn release_milestone(&mut self, grant_id: u64, milestone_index: u32) {
+        self.require_permission(&env::predecessor_account_id(), Permission::ReviewGrants);
+        self.internal_release_milestone(grant_id, milestone_index);
+    }
+
+    /// Meldet die ausgezahlte Tranche bzw. setzt sie bei fehlgeschlagener Überweisung wieder auf
+    /// Submitted. Wurde der Grant inzwischen abgebrochen, geht der Betrag an die Treasury zurück.
+    #[private]
+    pub fn on_grant_payout(&mut self, grant_id: u64, milestone_index: u32) -> bool {
+        let succeeded = is_promise_success();
+        if succeeded {
+            if let Some(grant) = self.grants.get(&grant_id) {
+                MilestoneReleased {
+                    grant_id,
+                    milestone_index,
+                    grantee: &grant.grantee,
+                    amount: grant.milestones[milestone_index as usize].amount,
+                }
+                .emit();
+            }
+        } else {
+            if let Some(mut grant) = self.grants.get(&grant_id) {
+                let milestone = &mut grant.milestones[milestone_index as usize];
+                milestone.status = MilestoneStatus::Submitted;
+                milestone.released_at = None;
+                let amount = milestone.amount.0;
+                if grant.cancelled {
+                    self.internal_return_to_treasury(&grant.asset, amount, format!("Grant #{} cancelled", grant_id));
+                }
+                self.grants.insert(&grant_id, &grant);
+            }
+            env::log_str(&format!("Payout for grant #{} milestone {} failed", grant_id, milestone_index));
+        }
+        succeeded
+    }
+
+    pub fn get_grant(&self, grant_id: u64) -> Option<Grant> {
+        self.grants.get(&grant_id)
+    }
+
+    pub fn get_grants(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<Grant> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(50);
+        self.grants
+            .values()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ft_core::FungibleTokenCore;
+    use crate::test_utils::*;
+
+    const TRANCHE: u128 = 1_000;
+
+    fn dao_token() -> Asset {
+        Asset::Ft { token_id: dao() }
+    }
+
+    fn token_balance(contract: &Contract, account_id: &AccountId) -> u128 {
+        contract.accounts.get(account_id).unwrap_or(ZERO_TOKEN).as_yoctonear()
+    }
+
+    fn milestones(count: u128) -> Vec<MilestoneSpec> {
+        (1..=count)
+            .map(|i| MilestoneSpec { description: format!("Phase {}", i), amount: U128(i * TRANCHE) })
+            .collect()
+    }
+
+    /// Grant an Bob mit Tranchen über 1000, 2000 und 3000
+    fn setup_grant(asset: Asset) -> Contract {
+        let mut contract = setup();
+        if asset == Asset::Near {
+            set_context(&account("donor"), NearToken::from_near(10), START_MS);
+            contract.donate();
+        }
+        contract.internal_create_grant(account("bob"), asset, milestones(3));
+        contract
+    }
+
+    fn submit_and_release(contract: &mut Contract, milestone_index: u32) {
+        set_context(&account("bob"), ZERO_TOKEN, START_MS);
+        contract.submit_milestone(0, milestone_index, "https://example.org".to_string(), "hash".to_string());
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.internal_release_milestone(0, milestone_index);
+    }
+
+    #[test]
+    fn token_grants_are_held_in_escrow_without_registering_the_contract() {
+        let mut contract = setup_grant(dao_token());
+        assert_eq!(contract.escrow_tokens.as_yoctonear(), 6 * TRANCHE);
+        assert_eq!(token_balance(&contract, &treasury()), 3_000_000 * ONE_TOKEN - 6 * TRANCHE);
+
+        submit_and_release(&mut contract, 1);
+        assert_eq!(event_count("milestone_released"), 1);
+        assert_eq!(token_balance(&contract, &account("bob")), 2 * TRANCHE);
+        assert_eq!(contract.escrow_tokens.as_yoctonear(), 4 * TRANCHE);
+
+        assert!(contract.accounts.get(&dao()).is_none());
+        assert!(contract.get_roles(dao()).is_empty());
+        assert!(contract.get_all_balances().iter().all(|(account_id, _)| *account_id != dao()));
+        assert_eq!(contract.ft_total_supply().0, 10_000_000 * ONE_TOKEN);
+    }
+
+    #[test]
+    #[should_panic(expected = "MilestoneNotSubmitted")]
+    fn milestones_must_be_submitted_before_release() {
+        let mut contract = setup_grant(dao_token());
+        contract.internal_release_milestone(0, 0);
+    }
+
+    #[test]
+    fn cancel_refunds_only_unreleased_tranches() {
+        let mut contract = setup_grant(dao_token());
+        submit_and_release(&mut contract, 0);
+
+        contract.internal_cancel_grant(0);
+        assert_eq!(token_balance(&contract, &treasury()), 3_000_000 * ONE_TOKEN - TRANCHE);
+        assert_eq!(token_balance(&contract, &account("bob")), TRANCHE);
+        assert_eq!(contract.escrow_tokens, ZERO_TOKEN);
+    }
+
+    #[test]
+    fn cancel_refunds_near_to_the_ledger() {
+        let mut contract = setup_grant(Asset::Near);
+        let ledger_after_funding = contract.internal_treasury_balance(&Asset::Near);
+        submit_and_release(&mut contract, 2);
+        assert_eq!(near_transfers(), vec![(account("bob"), 3 * TRANCHE)]);
+
+        contract.internal_cancel_grant(0);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding + 3 * TRANCHE);
+    }
+
+    #[test]
+    fn failed_payout_resets_the_milestone() {
+        let mut contract = setup_grant(Asset::Near);
+        let ledger_after_funding = contract.internal_treasury_balance(&Asset::Near);
+        submit_and_release(&mut contract, 0);
+
+        assert_eq!(event_count("milestone_released"), 0);
+        set_callback_context(false, START_MS);
+        assert!(!contract.on_grant_payout(0, 0));
+        assert_eq!(event_count("milestone_released"), 0);
+        let milestone = &contract.get_grant(0).unwrap().milestones[0];
+        assert_eq!(milestone.status, MilestoneStatus::Submitted);
+        assert_eq!(milestone.released_at, None);
+        // Noch reserviert, kann erneut freigegeben werden
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding);
+    }
+
+    #[test]
+    fn failed_payout_after_cancel_returns_the_tranche() {
+        let mut contract = setup_grant(Asset::Near);
+        let ledger_after_funding = contract.internal_treasury_balance(&Asset::Near);
+        submit_and_release(&mut contract, 0);
+        contract.internal_cancel_grant(0);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding + 5 * TRANCHE);
+
+        set_callback_context(false, START_MS);
+        assert!(!contract.on_grant_payout(0, 0));
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding + 6 * TRANCHE);
+    }
+}
@@ -8,29 +8,33 @@ impl Contract {
         match self.accounts.get(account_id) {
             Some(balance) => balance,
             None => {
-                env::panic_str(format!("The account {} is not registered", &account_id).as_str())
+                DaoError::AccountNotRegistered.panic_with(account_id)
             }
         }
     }
 
     pub(crate) fn internal_deposit(&mut self, account_id: &AccountId, amount: NearToken) {
         let balance = self.internal_unwrap_balance_of(account_id);
+        self.internal_snapshot_election_balance(account_id, balance);
         if let Some(new_balance) = balance.checked_add(amount) {
             self.accounts.insert(account_id, &new_balance);
             if !self.registered_accounts.iter().any(|a| a == *account_id) {
                 self.registered_accounts.push(account_id);
             }
+            self.internal_update_role_tiers(account_id);
         } else {
-            env::panic_str("Balance overflow");
+            DaoError::BalanceOverflow.panic_with(account_id);
         }
     }
 
     pub(crate) fn internal_withdraw(&mut self, account_id: &AccountId, amount: NearToken) {
         let balance = self.internal_unwrap_balance_of(account_id);
+        self.internal_snapshot_election_balance(account_id, balance);
         if let Some(new_balance) = balance.checked_sub(amount) {
             self.accounts.insert(account_id, &new_balance);
+            self.internal_update_role_tiers(account_id);
         } else {
-            env::panic_str("The account doesn't have enough balance");
+            DaoError::InsufficientBalance.panic_with(account_id);
         }
     }
 
@@ -43,11 +47,11 @@ impl Contract {
     ) {
         require!(
             sender_id != receiver_id,
-            "Sender and receiver should be different"
+            DaoError::SameSenderAndReceiver.to_string()
         );
         require!(
             amount.gt(&ZERO_TOKEN),
-            "The amount should be a positive number"
+            DaoError::NonPositiveAmount.to_string()
         );
 
         self.internal_withdraw(sender_id, amount);
@@ -60,6 +64,152 @@ impl Contract {
             memo: memo.as_deref(),
         }
         .emit();
+        self.internal_record_activity(
+            ActivityKind::Transfer,
+            sender_id,
+            Some(receiver_id),
+            Some(amount.as_yoctonear()),
+            memo,
+        );
+    }
+
+    pub(crate) fn assert_proposal_executable(&self, proposal_id: u64) -> Proposal {
+        let proposal = self
+            .proposals
+            .get(&proposal_id)
+            .unwrap_or_else(|| DaoError::ProposalNotFound.panic_with(proposal_id));
+        require!(proposal.status == ProposalStatus::Accepted, DaoError::ProposalNotAccepted.to_string());
+        require!(!proposal.executed, DaoError::ProposalAlreadyExecuted.to_string());
+        proposal
+    }
+
+    pub(crate) fn internal_execute_proposal(&mut self, proposal_id: u64) {
+        let mut proposal = self.assert_proposal_executable(proposal_id);
+        // Kategorisierte Auszahlungen werden gegen das passende Budget gebucht
+        if let (Some(category), Some((asset, amount))) = (proposal.category.clone(), proposal.payout()) {
+            self.internal_budget_spend(&category, &asset, amount);
+        }
+        match proposal.kind.clone() {
+            ProposalKind::Transfer => self.internal_execute_transfer(&proposal),
+            ProposalKind::SetRolePermissions { role, permissions } => {
+                self.internal_set_role_permissions(&role, permissions)
+            }
+            ProposalKind::RemoveRole { role } => self.internal_remove_role_definition(&role),
+            ProposalKind::ApproveMembership { application_id } => {
+                self.internal_approve_application(application_id)
+            }
+            ProposalKind::StartElection { seats, mode, nomination_period_ms, voting_period_ms, term_ms } => {
+                self.internal_start_election(seats, mode, nomination_period_ms, voting_period_ms, term_ms)
+            }
+            ProposalKind::ChangeDaoAccounts { treasury_account, team_account } => {
+                self.internal_change_dao_accounts(treasury_account, team_account)
+            }
+            ProposalKind::UpdateConfig { update } => self.internal_update_config(update),
+            ProposalKind::NearTransfer { receiver_id, amount } => {
+                self.internal_treasury_payout(proposal_id, Asset::Near, receiver_id, amount.0)
+                    .detach();
+            }
+            ProposalKind::TreasuryTransfer { asset, receiver_id, amount } => {
+                self.internal_treasury_payout(proposal_id, asset, receiver_id, amount.0)
+                    .detach();
+            }
+            ProposalKind::SetBudget { category, asset, amount, period_sec, rollover } => {
+                self.internal_set_budget(category, asset, amount, period_sec, rollover)
+            }
+            ProposalKind::RemoveBudget { category, asset } => self.internal_remove_budget(category, asset),
+            ProposalKind::SetAllowance { account_id, per_tx_max, per_period_max, period_sec, allowed_recipients } => {
+                self.internal_set_allowance(account_id, per_tx_max, per_period_max, period_sec, allowed_recipients)
+            }
+            ProposalKind::RevokeAllowance { account_id } => self.internal_revoke_allowance(account_id),
+            ProposalKind::CreateStream { recipient, asset, rate_per_sec, start, end, cliff } => {
+                self.internal_create_stream(recipient, asset, rate_per_sec, start, end, cliff)
+            }
+            ProposalKind::CancelStream { stream_id } => self.internal_cancel_stream(stream_id),
+            ProposalKind::CreateGrant { grantee, asset, milestones } => {
+                self.internal_create_grant(grantee, asset, milestones)
+            }
+            ProposalKind::ReleaseMilestone { grant_id, milestone_index } => {
+                self.internal_release_milestone(grant_id, milestone_index)
+            }
+            ProposalKind::CancelGrant { grant_id } => self.internal_cancel_grant(grant_id),
+            ProposalKind::CreateBounty { description, asset, amount, max_claims, deadline, claim_period_sec } => {
+                self.internal_create_bounty(description, asset, amount, max_claims, deadline, claim_period_sec)
+            }
+            ProposalKind::ApproveBountyClaim { bounty_id, claimant } => {
+                self.internal_approve_bounty_claim(bounty_id, claimant)
+            }
+            ProposalKind::CancelBounty { bounty_id } => self.internal_cancel_bounty(bounty_id),
+            ProposalKind::Unpause { subsystem } => self.internal_set_paused(subsystem, false),
+            ProposalKind::UpgradeContract { code_hash } => self.internal_schedule_upgrade(proposal_id, code_hash),
+        }
+        proposal.executed = true;
+        self.proposals.insert(&proposal_id, &proposal);
+        // Treasury-Auszahlungen gelten erst nach dem Callback als ausgeführt (on_treasury_payout)
+        if !proposal.has_async_payout() {
+            ProposalExecuted {
+                proposal_id,
+                kind: &proposal.kind,
+            }
+            .emit();
+        }
+    }
+
+    pub(crate) fn internal_distribute_dividends(&mut self, total_amount_yocto: u128) {
+        let total_supply = self.total_supply.as_yoctonear();
+        require!(total_supply > 0, DaoError::NoCirculatingSupply.to_string());
+
+        let mut recipients = 0;
+        for (account_id, balance_token) in self.accounts.iter() {
+            let balance = balance_token.as_yoctonear();
+            let share = balance * total_amount_yocto / total_supply;
+            if share > 0 {
+                Promise::new(account_id.clone())
+                    .transfer(NearToken::from_yoctonear(share))
+                    .detach();
+                recipients += 1;
+            }
+        }
+        DividendsDistributed {
+            total_amount: U128(total_amount_yocto),
+            total_supply: U128(total_supply),
+            recipients,
+        }
+        .emit();
+        self.internal_record_activity(
+            ActivityKind::Dividend,
+            &env::predecessor_account_id(),
+            None,
+            Some(total_amount_yocto),
+            None,
+        );
+    }
+
+    /// Auszahlung eines akzeptierten Transfer-Proposals vom Treasury-Account
+    pub(crate) fn internal_execute_transfer(&mut self, proposal: &Proposal) {
+        let amount = proposal
+            .amount
+            .unwrap_or_else(|| DaoError::MissingTransferAmount.panic_with(proposal.id));
+        let target = proposal
+            .target_account
+            .clone()
+            .unwrap_or_else(|| DaoError::MissingTransferTarget.panic_with(proposal.id));
+        let treasury_account_id = self.treasury_account_id();
+        require!(
+            self.accounts.get(&treasury_account_id).unwrap_or(ZERO_TOKEN).as_yoctonear() >= amount,
+            DaoError::InsufficientTreasury.to_string()
+        );
+        let target_account: AccountId = target
+            .parse()
+            .unwrap_or_else(|_| DaoError::InvalidTargetAccount.panic_with(&target));
+        if self.accounts.get(&target_account).is_none() {
+            self.internal_register_account(&target_account);
+        }
+        self.internal_transfer(
+            &treasury_account_id,
+            &target_account,
+            NearToken::from_yoctonear(amount),
+            Some(format!("Proposal #{}", proposal.id)),
+        );
     }
 
     pub(crate) fn internal_register_account(&mut self, account_id: &AccountId) {
@@ -67,10 +217,10 @@ impl Contract {
             self.accounts.insert(account_id, &ZERO_TOKEN);
             self.registered_accounts.push(account_id);
             if self.roles.get(account_id).is_none() {
-                self.roles.insert(account_id, &ROLE_VISITOR.to_string());
+                self.internal_grant_role(account_id, ROLE_VISITOR, None, None);
             }
         } else {
-            env::panic_str("The account is already registered");
+            DaoError::AccountAlreadyRegistered.panic_with(account_id);
         }
     }
 
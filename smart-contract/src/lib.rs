@@ -12,24 +12,57 @@
 use near_sdk::assert_one_yocto;
 use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use near_sdk::collections::UnorderedMap;
-use near_sdk::collections::{LazyOption, LookupMap, Vector};
-use near_sdk::json_types::U128;
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet, Vector};
+use near_sdk::json_types::{Base58CryptoHash, U128};
 use near_sdk::require;
 use near_sdk::{
     env, near_bindgen, AccountId, BorshStorageKey, NearSchema, NearToken, PanicOnDefault, Promise,
     StorageUsage,
 };
 
+pub mod activity;
+pub mod allowances;
+pub mod bounties;
+pub mod budgets;
+pub mod config;
+pub mod elections;
+pub mod errors;
 pub mod events;
 pub mod ft_core;
+pub mod grants;
 pub mod internal;
+pub mod membership;
 pub mod metadata;
+pub mod migration;
+pub mod multisig;
+pub mod pause;
 pub mod proposal;
+pub mod roles;
 pub mod storage;
-
+pub mod streams;
+#[cfg(test)]
+mod test_utils;
+pub mod treasury;
+pub mod upgrade;
+
+use crate::activity::{ActivityKind, ActivityRecord};
+use crate::allowances::{Allowance, FinanceSpendRecord};
+use crate::bounties::{bounty_total, Bounty};
+use crate::budgets::Budget;
+use crate::config::DaoConfig;
+use crate::elections::Election;
+use crate::errors::DaoError;
+use crate::grants::Grant;
 use crate::events::*;
+use crate::membership::MembershipApplication;
 use crate::metadata::*;
-use crate::proposal::{Proposal, ProposalStatus};
+use crate::multisig::{PendingAction, SensitiveAction};
+use crate::pause::Subsystem;
+use crate::proposal::{Proposal, ProposalKind, ProposalStatus};
+use crate::roles::{default_permission_matrix, Permission, RoleChange, RoleGrant};
+use crate::streams::PaymentStream;
+use crate::treasury::Asset;
+use crate::upgrade::ScheduledUpgrade;
 
 const DATA_IMAGE_SVG_GT_ICON: &str = "data:image/jpeg;base64,/9j/...";
 
@@ -37,7 +70,7 @@ pub const FT_METADATA_SPEC: &str = "ft-1.0.0";
 pub const ZERO_TOKEN: NearToken = NearToken::from_yoctonear(0);
 pub const USDT_TO_TOKEN_RATE: u128 = 1_000_000_000_000_000_000;
 pub const TOKEN_PRICE_CHF: u128 = 1; // 1 Token = 1 CHF
-pub const NEAR_TO_CHF_RATE: u128 = 5; // Beispiel: 1 NEAR = 5 CHF
+pub const NEAR_TO_CHF_RATE: u128 = 5; // Beispiel: 1 NEAR = 5 CHF (Standardwert für DaoConfig::sale_rate)
 
 // Token Distribution Constants
 pub const TOTAL_SUPPLY: u128 = 10_000_000_000_000_000_000_000_000; // 10M Tokens
@@ -45,15 +78,14 @@ pub const COMMUNITY_TREASURY_PERCENTAGE: u128 = 60; // 60%
 pub const TEAM_PERCENTAGE: u128 = 20; // 20%
 pub const INITIAL_SALE_PERCENTAGE: u128 = 20; // 20%
 
-// Feste Subaccounts für Treasury und Team
-pub const TREASURY_ACCOUNT: &str = "treasury.dao.lioneluser.testnet";
-pub const TEAM_ACCOUNT: &str = "team.dao.lioneluser.testnet";
-
 pub const ROLE_CORE: &str = "core";
 pub const ROLE_COMMUNITY: &str = "community";
 pub const ROLE_FINANCE: &str = "finance";
 pub const ROLE_VISITOR: &str = "visitor";
 
+// Standard-Schwelle für die automatische Community-Rolle
+pub const DEFAULT_COMMUNITY_THRESHOLD: u128 = 100_000_000_000_000_000_000_000_000; // 100 Tokens
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 #[borsh(crate = "near_sdk::borsh")]
@@ -66,11 +98,45 @@ pub struct Contract {
     pub proposal_ids: Vector<u64>,
     pub next_proposal_id: u64,
     pub registered_accounts: Vector<AccountId>,
-    pub roles: LookupMap<AccountId, String>,
+    pub roles: LookupMap<AccountId, Vec<RoleGrant>>,
     pub token_pool: NearToken,
     pub community_treasury: NearToken,
     pub team_tokens: NearToken,
     pub team_accounts: Vector<AccountId>,
+    pub role_permissions: UnorderedMap<String, Vec<Permission>>,
+    pub core_members: UnorderedSet<AccountId>,
+    pub role_history: Vector<RoleChange>,
+    pub applications: LookupMap<u64, MembershipApplication>,
+    pub pending_applications: UnorderedSet<u64>,
+    pub application_by_account: LookupMap<AccountId, u64>,
+    pub next_application_id: u64,
+    pub elections: Vector<Election>,
+    pub election_voters: LookupMap<AccountId, u64>,
+    /// Guthaben bei Öffnung der Wahl (term_id, Guthaben), erfasst vor der ersten Änderung im Wahlfenster
+    pub election_snapshots: LookupMap<AccountId, (u64, NearToken)>,
+    pub council: Vec<AccountId>,
+    pub config: DaoConfig,
+    pub treasury_holdings: UnorderedMap<Asset, u128>,
+    pub budgets: UnorderedMap<(String, Asset), Budget>,
+    pub allowances: UnorderedMap<AccountId, Allowance>,
+    pub finance_spends: Vector<FinanceSpendRecord>,
+    pub pending_actions: UnorderedMap<u64, PendingAction>,
+    pub next_action_id: u64,
+    pub streams: UnorderedMap<u64, PaymentStream>,
+    pub next_stream_id: u64,
+    pub grants: UnorderedMap<u64, Grant>,
+    pub next_grant_id: u64,
+    pub bounties: UnorderedMap<u64, Bounty>,
+    pub next_bounty_id: u64,
+    /// Für Streams, Grants und Bounties reservierte DAO-Tokens. Werden nicht als Guthaben des
+    /// Contract-Accounts geführt, damit dieser weder Rollen noch Dividenden erhält.
+    pub escrow_tokens: NearToken,
+    pub activity_log: LookupMap<u64, ActivityRecord>,
+    pub activity_count: u64,
+    pub account_activity: LookupMap<AccountId, Vec<u64>>,
+    pub paused: Vec<Subsystem>,
+    pub upgrade_code: LookupMap<Base58CryptoHash, Vec<u8>>,
+    pub upgrade: Option<ScheduledUpgrade>,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
@@ -82,6 +148,26 @@ pub enum StorageKey {
     ProposalIds,
     Roles,
     TeamVesting,
+    RolePermissions,
+    CoreMembers,
+    RoleHistory,
+    Applications,
+    PendingApplications,
+    ApplicationByAccount,
+    Elections,
+    ElectionVoters,
+    ElectionSnapshots,
+    TreasuryHoldings,
+    Budgets,
+    Allowances,
+    FinanceSpends,
+    PendingActions,
+    Streams,
+    Grants,
+    Bounties,
+    ActivityLog,
+    AccountActivity,
+    UpgradeCode,
 }
 
 #[near_bindgen]
@@ -102,10 +188,13 @@ impl Contract {
     /// Erstellt den Contract mit Standard-Metadaten (Name, Symbol, Decimals, Icon)
     /// owner_id: Account, der als Owner/Core startet
     /// total_supply: Gesamtmenge an Tokens (in Yocto)
+    /// treasury_account_id / team_account_id: Accounts für Treasury- und Team-Anteil
     #[init]
     pub fn new_default_meta(
         owner_id: AccountId,
-        total_supply: U128
+        total_supply: U128,
+        treasury_account_id: AccountId,
+        team_account_id: AccountId,
     ) -> Self {
         Self::new(
             owner_id,
@@ -119,6 +208,8 @@ impl Contract {
                 reference_hash: None,
                 decimals: 24,
             },
+            treasury_account_id,
+            team_account_id,
         )
     }
 
@@ -127,12 +218,20 @@ impl Contract {
     /// - owner_id: Account, der als Core startet
     /// - total_supply: Gesamtmenge an Tokens (in Yocto)
     /// - metadata: Token-Metadaten (Name, Symbol, Decimals, etc.)
+    /// - treasury_account_id / team_account_id: Accounts für Treasury- und Team-Anteil (per Proposal änderbar)
     #[init]
     pub fn new(
         owner_id: AccountId,
         total_supply: U128,
-        metadata: FungibleTokenMetadata
+        metadata: FungibleTokenMetadata,
+        treasury_account_id: AccountId,
+        team_account_id: AccountId,
     ) -> Self {
+        require!(
+            treasury_account_id != team_account_id,
+            DaoError::TreasuryEqualsTeam.to_string()
+        );
+
         // Tokenverteilung berechnen
         let total_supply_yocto = total_supply.0; // z.B. 10_000_000 * 10^24
         let token_pool = NearToken::from_yoctonear((total_supply_yocto * 60) / 100); // 60% für Verkauf
@@ -154,65 +253,119 @@ impl Contract {
             next_proposal_id: 0,
             roles: LookupMap::new(StorageKey::Roles),
             team_accounts: Vector::new(b"t".to_vec()),
+            role_permissions: UnorderedMap::new(StorageKey::RolePermissions),
+            core_members: UnorderedSet::new(StorageKey::CoreMembers),
+            role_history: Vector::new(StorageKey::RoleHistory),
+            applications: LookupMap::new(StorageKey::Applications),
+            pending_applications: UnorderedSet::new(StorageKey::PendingApplications),
+            application_by_account: LookupMap::new(StorageKey::ApplicationByAccount),
+            next_application_id: 0,
+            elections: Vector::new(StorageKey::Elections),
+            election_voters: LookupMap::new(StorageKey::ElectionVoters),
+            election_snapshots: LookupMap::new(StorageKey::ElectionSnapshots),
+            council: vec![],
+            config: DaoConfig::new(treasury_account_id.clone(), team_account_id.clone()),
+            treasury_holdings: UnorderedMap::new(StorageKey::TreasuryHoldings),
+            budgets: UnorderedMap::new(StorageKey::Budgets),
+            allowances: UnorderedMap::new(StorageKey::Allowances),
+            finance_spends: Vector::new(StorageKey::FinanceSpends),
+            pending_actions: UnorderedMap::new(StorageKey::PendingActions),
+            next_action_id: 0,
+            streams: UnorderedMap::new(StorageKey::Streams),
+            next_stream_id: 0,
+            grants: UnorderedMap::new(StorageKey::Grants),
+            next_grant_id: 0,
+            bounties: UnorderedMap::new(StorageKey::Bounties),
+            next_bounty_id: 0,
+            escrow_tokens: ZERO_TOKEN,
+            activity_log: LookupMap::new(StorageKey::ActivityLog),
+            activity_count: 0,
+            account_activity: LookupMap::new(StorageKey::AccountActivity),
+            paused: vec![],
+            upgrade_code: LookupMap::new(StorageKey::UpgradeCode),
+            upgrade: None,
         };
 
+        // Standard-Permission-Matrix setzen (später per Proposal änderbar)
+        for (role, permissions) in default_permission_matrix() {
+            this.role_permissions.insert(&role.to_string(), &permissions);
+        }
+
         // Storage für Account-IDs messen (für Gebühren)
         this.measure_bytes_for_longest_account_id();
+        migration::write_state_version();
         // Owner, Treasury und Team als Accounts registrieren
-        let treasury_account_id: AccountId = TREASURY_ACCOUNT.parse().unwrap();
-        let team_account_id: AccountId = TEAM_ACCOUNT.parse().unwrap();
         this.internal_register_account(&owner_id);
         this.internal_register_account(&treasury_account_id);
         this.internal_register_account(&team_account_id);
         
+        // Rollen zuweisen (vor der Verteilung, damit keine automatischen Stufen vergeben werden)
+        this.internal_grant_role(&owner_id, ROLE_CORE, None, Some("Contract owner".to_string()));
+        this.internal_grant_role(&treasury_account_id, ROLE_FINANCE, None, Some("Treasury account".to_string()));
+        this.internal_grant_role(&team_account_id, ROLE_CORE, None, Some("Team account".to_string()));
+
         // Token verteilen
         this.internal_deposit(&treasury_account_id, treasury); // Treasury-Account
         this.internal_deposit(&team_account_id, team_tokens);  // Team-Account
 
-        // Rollen zuweisen
-        this.roles.insert(&owner_id, &ROLE_CORE.to_string());
-        this.roles.insert(&treasury_account_id, &ROLE_FINANCE.to_string());
-        this.roles.insert(&team_account_id, &ROLE_CORE.to_string());
-
-        // Event für Minting
-        FtMint {
-            owner_id: &owner_id,
-            amount: &NearToken::from_yoctonear(total_supply_yocto),
-            memo: Some("Initial token supply is minted"),
-        }
-        .emit();
+        // Mint-Events nur für tatsächlich zugeteilte Guthaben; der Verkaufs-Pool
+        // gehört niemandem und wird erst beim Kauf (buy_tokens) gemintet
+        FtMint::emit_many(&[
+            FtMint {
+                owner_id: &treasury_account_id,
+                amount: &treasury,
+                memo: Some("Initial treasury allocation"),
+            },
+            FtMint {
+                owner_id: &team_account_id,
+                amount: &team_tokens,
+                memo: Some("Initial team allocation"),
+            },
+        ]);
 
         this
     }
 
     #[payable]
     pub fn buy_tokens(&mut self) {
+        self.assert_not_paused(Subsystem::Sale);
         let buyer = env::predecessor_account_id();
         let deposit: NearToken = env::attached_deposit();
         require!(
             deposit.as_yoctonear() > 0,
-            "You must send a positive amount of NEAR"
+            DaoError::ZeroDeposit.to_string()
         );
 
+        // Verkaufserlös in NEAR wird im Treasury-Ledger verbucht
+        self.internal_treasury_credit(&Asset::Near, deposit.as_yoctonear());
+
         // Registrierung sicherstellen
         if self.accounts.get(&buyer).is_none() {
             self.internal_register_account(&buyer);
         }
 
         // Token-Betrag berechnen und gutschreiben
-        let tokens_to_buy = deposit.as_yoctonear() * NEAR_TO_CHF_RATE;
+        let tokens_to_buy = deposit.as_yoctonear() * self.config.sale_rate.0;
         self.internal_deposit(&buyer, NearToken::from_yoctonear(tokens_to_buy));
+        FtMint {
+            owner_id: &buyer,
+            amount: &NearToken::from_yoctonear(tokens_to_buy),
+            memo: Some("Token sale"),
+        }
+        .emit();
+        self.internal_record_activity(ActivityKind::Buy, &buyer, None, Some(tokens_to_buy), None);
         self.token_pool = NearToken::from_yoctonear(self.token_pool.as_yoctonear() - tokens_to_buy);
-
-        // Rolle nur beim ersten Kauf setzen (wenn noch keine oder visitor)
-        let current_role = self.roles.get(&buyer);
-        if current_role.is_none() || current_role.as_deref() == Some(ROLE_VISITOR) {
-            self.roles.insert(&buyer, &ROLE_COMMUNITY.to_string());
+        TokensPurchased {
+            buyer: &buyer,
+            near_amount: U128(deposit.as_yoctonear()),
+            token_amount: U128(tokens_to_buy),
         }
+        .emit();
+        // Rollen-Stufe (z.B. community) wird in internal_deposit anhand der DaoConfig::role_thresholds gesetzt
     }
 
     #[payable]
-    #[payable]
+    #[allow(clippy::too_many_arguments)]
     pub fn create_proposal(
         &mut self,
         title: String,
@@ -225,16 +378,32 @@ impl Contract {
         deadline: Option<u64>,
         required_role: Option<String>,
         quorum: Option<U128>,
-    ) {
+        kind: Option<ProposalKind>,
+    ) -> u64 {
         let proposer = env::predecessor_account_id();
-        let role = self.roles.get(&proposer).unwrap_or_else(|| "".to_string());
+        self.require_permission(&proposer, Permission::CreateProposal);
+        let bond = self.config.proposal_bond.0;
+        let deposit = env::attached_deposit().as_yoctonear();
+        require!(deposit >= bond, DaoError::InsufficientProposalBond.to_string());
+        // Der Bond wird bis zur Finalisierung gehalten, Überschuss sofort zurück
+        if deposit > bond {
+            Promise::new(proposer.clone())
+                .transfer(NearToken::from_yoctonear(deposit - bond))
+                .detach();
+        }
 
-        require!(
-            role == ROLE_CORE || role == ROLE_COMMUNITY,
-            "Nur Community- oder Core-Mitglieder dürfen Proposals erstellen"
-        );
+        // Überlauf beim Bounty-Gesamtbetrag schon bei der Erstellung abweisen, nicht erst bei der Ausführung
+        if let Some(ProposalKind::CreateBounty { amount, max_claims, .. }) = &kind {
+            bounty_total(*amount, *max_claims);
+        }
 
         let proposal_id = self.next_proposal_id;
+        // Fehlende Deadline (Sekunden) und Quorum aus der DaoConfig übernehmen
+        let deadline = deadline.or_else(|| {
+            self.config
+                .voting_period_sec
+                .map(|period| env::block_timestamp_ms() / 1000 + period)
+        });
 
         let proposal = Proposal {
             id: proposal_id,
@@ -254,12 +423,24 @@ impl Contract {
             category,
             deadline,
             required_role,
-            quorum: quorum.map(|q| q.0), // ✅ U128 -> u128
+            quorum: quorum.or(self.config.default_quorum).map(|q| q.0), // ✅ U128 -> u128
+            kind: kind.unwrap_or(ProposalKind::Transfer),
+            bond: U128(bond),
         };
 
         self.proposals.insert(&proposal_id, &proposal);
         self.proposal_ids.push(&proposal_id);
         self.next_proposal_id += 1;
+        ProposalCreated {
+            proposal_id,
+            proposer: &proposer,
+            title: &proposal.title,
+            kind: &proposal.kind,
+            deadline: proposal.deadline,
+        }
+        .emit();
+        self.internal_record_activity(ActivityKind::Proposal, &proposer, None, None, Some(proposal_id.to_string()));
+        proposal_id
     }
 
     pub fn get_proposals(&self) -> Vec<Proposal> {
@@ -277,26 +458,27 @@ impl Contract {
     pub fn vote_on_proposal(&mut self, proposal_id: u64, support: bool) {
         assert_one_yocto();
         let voter = env::predecessor_account_id();
-
-        let role = self.roles.get(&voter).unwrap_or_else(|| "none".to_string());
-        require!(
-            role == "community" || role == "core" || role == "finance",
-            "Only community, core or finance members can vote"
-        );
+        self.require_permission(&voter, Permission::Vote);
 
         let mut proposal = self
             .proposals
             .get(&proposal_id)
-            .expect("Proposal not found");
+            .unwrap_or_else(|| DaoError::ProposalNotFound.panic_with(proposal_id));
 
         require!(
             proposal.status == ProposalStatus::Open,
-            "Proposal is already finalized"
+            DaoError::ProposalAlreadyFinalized.to_string()
         );
+        if let Some(deadline) = proposal.deadline {
+            require!(
+                env::block_timestamp_ms() / 1000 <= deadline,
+                DaoError::VotingDeadlinePassed.to_string()
+            );
+        }
 
         let already_voted = proposal.votes_for.iter().any(|(acc, _)| acc == &voter)
             || proposal.votes_against.iter().any(|(acc, _)| acc == &voter);
-        require!(!already_voted, "You have already voted");
+        require!(!already_voted, DaoError::AlreadyVoted.to_string());
 
         let voter_balance = self
             .accounts
@@ -313,40 +495,74 @@ impl Contract {
         }
 
         self.proposals.insert(&proposal_id, &proposal);
+        ProposalVoted {
+            proposal_id,
+            voter: &voter,
+            support,
+            weight: U128(voter_balance),
+        }
+        .emit();
+        self.internal_record_activity(
+            ActivityKind::Vote,
+            &voter,
+            None,
+            Some(voter_balance),
+            Some(proposal_id.to_string()),
+        );
     }
 
     pub fn finalize_proposal(&mut self, proposal_id: u64) {
         let caller = env::predecessor_account_id();
-
-        let role = self
-            .roles
-            .get(&caller)
-            .unwrap_or_else(|| "none".to_string());
-        require!(
-            role == "core" || role == "finance",
-            "Only core or finance members can finalize proposals"
-        );
+        self.require_permission(&caller, Permission::Finalize);
 
         let mut proposal = self
             .proposals
             .get(&proposal_id)
-            .expect("Proposal not found");
+            .unwrap_or_else(|| DaoError::ProposalNotFound.panic_with(proposal_id));
 
         require!(
             proposal.status == ProposalStatus::Open,
-            "Proposal is already finalized"
+            DaoError::ProposalAlreadyFinalized.to_string()
         );
 
         let for_votes: u128 = proposal.votes_for.iter().map(|(_, w)| *w).sum();
         let against_votes: u128 = proposal.votes_against.iter().map(|(_, w)| *w).sum();
 
-        proposal.status = if for_votes > against_votes {
+        let quorum_reached = proposal
+            .quorum
+            .is_none_or(|quorum| for_votes + against_votes >= quorum);
+
+        proposal.status = if quorum_reached && for_votes > against_votes {
             ProposalStatus::Accepted
         } else {
             ProposalStatus::Rejected
         };
 
+        // Bond zurück an den Proposer, sobald das Quorum erreicht wurde; ohne Quorum (Spam) verfällt er an die Treasury
+        let bond_refunded = quorum_reached;
+        if proposal.bond.0 > 0 {
+            if bond_refunded {
+                let proposer: AccountId = proposal
+                    .proposer
+                    .parse()
+                    .unwrap_or_else(|_| DaoError::InvalidTargetAccount.panic_with(&proposal.proposer));
+                Promise::new(proposer)
+                    .transfer(NearToken::from_yoctonear(proposal.bond.0))
+                    .detach();
+            } else {
+                self.internal_treasury_credit(&Asset::Near, proposal.bond.0);
+            }
+        }
+
         self.proposals.insert(&proposal_id, &proposal);
+        ProposalFinalized {
+            proposal_id,
+            status: &proposal.status,
+            votes_for: U128(for_votes),
+            votes_against: U128(against_votes),
+            bond_refunded,
+        }
+        .emit();
     }
 
     pub fn get_all_balances(&self) -> Vec<(AccountId, U128)> {
@@ -359,46 +575,48 @@ impl Contract {
             .collect()
     }
 
+    /// Weist eine Rolle zu. `expires_at` (ms) begrenzt die Amtszeit; "visitor" entzieht alle Rollen.
+    /// Core ist nach der ersten abgeschlossenen Wahl nur noch über Wahlen erhältlich.
     #[payable]
-    pub fn assign_role(&mut self, account_id: AccountId, role: String) {
+    pub fn assign_role(
+        &mut self,
+        account_id: AccountId,
+        role: String,
+        expires_at: Option<u64>,
+        reason: Option<String>,
+    ) {
         let caller = env::predecessor_account_id();
-        let caller_role = self.roles.get(&caller).unwrap_or_default();
-        assert_eq!(caller_role, ROLE_CORE, "Only core members can assign roles");
+        self.require_permission(&caller, Permission::ManageRoles);
 
-        assert!(
-            role == ROLE_CORE || role == ROLE_COMMUNITY || role == ROLE_FINANCE,
-            "Invalid role"
+        require!(self.role_permissions.get(&role).is_some(), DaoError::InvalidRole.with_detail(&role));
+        require!(
+            role != ROLE_CORE || !self.internal_council_elected(),
+            DaoError::CoreRoleIsElected.to_string()
         );
 
-        self.roles.insert(&account_id, &role);
+        self.internal_grant_role(&account_id, &role, expires_at, reason);
     }
 
+    /// Haupt-Rolle eines Accounts (core > finance > community > visitor), siehe get_roles für alle Rollen
     pub fn get_role(&self, account_id: AccountId) -> Option<String> {
-        self.roles.get(&account_id)
+        self.internal_primary_role(&account_id)
     }
 
     pub fn get_all_roles(&self) -> Vec<(AccountId, String)> {
         self.registered_accounts
             .iter()
-            .filter_map(|account| self.roles.get(&account).map(|role| (account, role)))
+            .filter_map(|account| self.internal_primary_role(&account).map(|role| (account, role)))
             .collect()
     }
 
+    /// Schüttet den angehängten NEAR-Betrag anteilig an alle Token-Halter aus.
+    /// Bei aktivem Multisig wird der Betrag bis zur M-ten Freigabe zurückgehalten.
     #[payable]
     pub fn distribute_dividends(&mut self) {
-        let total_supply = self.total_supply.as_yoctonear();
-        assert!(total_supply > 0, "Kein zirkulierender Supply");
-
-        let total_amount = env::attached_deposit();
-        let total_amount_yocto = total_amount.as_yoctonear();
-
-        for (account_id, balance_token) in self.accounts.iter() {
-            let balance = balance_token.as_yoctonear();
-            let share = balance * total_amount_yocto / total_supply;
-            if share > 0 {
-                Promise::new(account_id.clone()).transfer(NearToken::from_yoctonear(share));
-            }
-        }
+        self.require_permission(&env::predecessor_account_id(), Permission::DistributeDividends);
+        self.assert_not_paused(Subsystem::Dividends);
+        let amount = U128(env::attached_deposit().as_yoctonear());
+        self.internal_submit_action(SensitiveAction::DistributeDividends { amount });
     }
 
     /// Gibt die Gesamtmenge aller Tokens zurück (bleibt immer gleich)
@@ -413,47 +631,29 @@ impl Contract {
 
     // Fügt einen Account als Team-Mitglied hinzu (nur Core)
     pub fn add_team_member(&mut self, account_id: AccountId) {
-        require!(
-            self.roles.get(&env::predecessor_account_id()).unwrap_or_default() == ROLE_CORE,
-            "Only core members can add team members"
-        );
+        self.require_permission(&env::predecessor_account_id(), Permission::ManageTeam);
         self.internal_register_account(&account_id);
-        self.roles.insert(&account_id, &ROLE_CORE.to_string());
+        self.internal_grant_role(&account_id, ROLE_CORE, None, Some("Added to team".to_string()));
         // Team-Account hinzufügen, falls noch nicht vorhanden
         if !self.team_accounts.iter().any(|acc| acc == account_id) {
             self.team_accounts.push(&account_id);
+            TeamChanged { account_id: &account_id, added: true }.emit();
         }
     }
 
+    /// Führt ein akzeptiertes Proposal aus. Bei aktivem Multisig (multisig_threshold > 1)
+    /// zählt der Aufruf als Freigabe und die Ausführung erfolgt erst nach M Freigaben.
     pub fn execute_proposal(&mut self, proposal_id: u64) {
         let caller = env::predecessor_account_id();
-        let role = self.roles.get(&caller).unwrap_or_else(|| "none".to_string());
-        require!(role == ROLE_CORE || role == ROLE_FINANCE, "Only core or finance members can execute proposals");
-
-        let mut proposal = self.proposals.get(&proposal_id).expect("Proposal not found");
-        require!(proposal.status == ProposalStatus::Accepted, "Proposal not accepted");
-        require!(!proposal.executed, "Proposal already executed");
-        let amount = proposal.amount.expect("No amount specified");
-        let target = proposal.target_account.clone().expect("No target specified");
-        // Auszahlung erfolgt vom Treasury-Account
-        let treasury_account_id: AccountId = TREASURY_ACCOUNT.parse().unwrap();
-        require!(self.accounts.get(&treasury_account_id).unwrap_or(ZERO_TOKEN).as_yoctonear() >= amount, "Not enough in treasury account");
-        let target_account: AccountId = target.parse().expect("Invalid target account");
-        if self.accounts.get(&target_account).is_none() {
-            self.internal_register_account(&target_account);
-        }
-        self.internal_withdraw(&treasury_account_id, NearToken::from_yoctonear(amount));
-        self.internal_deposit(&target_account, NearToken::from_yoctonear(amount));
-        proposal.executed = true;
-        self.proposals.insert(&proposal_id, &proposal);
+        self.require_permission(&caller, Permission::Execute);
+        let proposal = self.assert_proposal_executable(proposal_id);
+        self.assert_governance_not_paused(&proposal);
+        self.internal_submit_action(SensitiveAction::ExecuteProposal { proposal_id });
     }
 
     // Entfernt ein Team-Mitglied (nur Core)
     pub fn remove_team_member(&mut self, account_id: AccountId) {
-        require!(
-            self.roles.get(&env::predecessor_account_id()).unwrap_or_default() == ROLE_CORE,
-            "Only core members can remove team members"
-        );
+        self.require_permission(&env::predecessor_account_id(), Permission::ManageTeam);
         let mut new_team = Vector::new(b"t2".to_vec());
         for acc in self.team_accounts.iter() {
             if acc != account_id {
@@ -461,6 +661,10 @@ impl Contract {
             }
         }
         self.team_accounts = new_team;
+        TeamChanged { account_id: &account_id, added: false }.emit();
+        if self.internal_has_role(&account_id, ROLE_CORE) {
+            self.internal_revoke_role(&account_id, ROLE_CORE, Some("Removed from team".to_string()));
+        }
     }
 
     // Gibt alle Team-Mitglieder zurück
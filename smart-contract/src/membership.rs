@@ -0,0 +1,179 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+use crate::storage::StorageManagement;
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, PartialEq, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum ApplicationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Mitgliedsantrag. Die Profildaten (Name, E-Mail, ...) bleiben off-chain in der Registry,
+/// on-chain wird nur deren Hash gespeichert.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct MembershipApplication {
+    pub id: u64,
+    pub applicant: AccountId,
+    pub profile_hash: String,
+    pub requested_role: String,
+    pub status: ApplicationStatus,
+    pub created_at: u64,
+    pub storage_deposit: NearToken,
+    pub decided_by: Option<AccountId>,
+    pub decided_at: Option<u64>,
+    pub reason: Option<String>,
+}
+
+impl Contract {
+    pub(crate) fn internal_approve_application(&mut self, application_id: u64) {
+        let mut application = self.internal_pending_application(application_id);
+
+        if self.accounts.get(&application.applicant).is_none() {
+            self.internal_register_account(&application.applicant);
+        }
+        self.internal_grant_role(
+            &application.applicant,
+            &application.requested_role,
+            None,
+            Some(format!("Membership application #{}", application_id)),
+        );
+
+        application.status = ApplicationStatus::Approved;
+        self.internal_close_application(application);
+    }
+
+    fn internal_pending_application(&self, application_id: u64) -> MembershipApplication {
+        let application = self
+            .applications
+            .get(&application_id)
+            .unwrap_or_else(|| DaoError::ApplicationNotFound.panic_with(application_id));
+        require!(
+            application.status == ApplicationStatus::Pending,
+            DaoError::ApplicationAlreadyDecided.with_detail(application_id)
+        );
+        application
+    }
+
+    fn internal_close_application(&mut self, mut application: MembershipApplication) {
+        let decided_by = env::predecessor_account_id();
+        application.decided_by = Some(decided_by.clone());
+        application.decided_at = Some(env::block_timestamp_ms());
+        self.pending_applications.remove(&application.id);
+        self.application_by_account.remove(&application.applicant);
+        self.applications.insert(&application.id, &application);
+
+        ApplicationDecided {
+            application_id: application.id,
+            applicant: &application.applicant,
+            status: &application.status,
+            decided_by: &decided_by,
+            reason: application.reason.as_deref(),
+        }
+        .emit();
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Stellt einen Mitgliedsantrag. Nicht registrierte Accounts hinterlegen die Storage-Gebühr,
+    /// die bei Ablehnung zurückerstattet wird.
+    #[payable]
+    pub fn apply_for_membership(&mut self, profile_hash: String, requested_role: String) -> u64 {
+        let applicant = env::predecessor_account_id();
+        require!(
+            requested_role != ROLE_VISITOR && self.role_permissions.get(&requested_role).is_some(),
+            DaoError::InvalidRole.with_detail(&requested_role)
+        );
+        require!(
+            !self.internal_has_role(&applicant, &requested_role),
+            DaoError::RoleAlreadyHeld.to_string()
+        );
+        require!(
+            self.application_by_account.get(&applicant).is_none(),
+            DaoError::ApplicationPending.to_string()
+        );
+
+        let deposit = env::attached_deposit();
+        let storage_deposit = if self.accounts.get(&applicant).is_none() {
+            let min_balance = self.storage_balance_bounds().min;
+            require!(
+                deposit >= min_balance,
+                DaoError::InsufficientStorageDeposit.with_detail(min_balance.as_yoctonear())
+            );
+            min_balance
+        } else {
+            ZERO_TOKEN
+        };
+        let refund = deposit.saturating_sub(storage_deposit);
+        if refund.gt(&ZERO_TOKEN) {
+            Promise::new(applicant.clone()).transfer(refund).detach();
+        }
+
+        let id = self.next_application_id;
+        let application = MembershipApplication {
+            id,
+            applicant: applicant.clone(),
+            profile_hash,
+            requested_role,
+            status: ApplicationStatus::Pending,
+            created_at: env::block_timestamp_ms(),
+            storage_deposit,
+            decided_by: None,
+            decided_at: None,
+            reason: None,
+        };
+        self.applications.insert(&id, &application);
+        self.pending_applications.insert(&id);
+        self.application_by_account.insert(&applicant, &id);
+        self.next_application_id += 1;
+
+        ApplicationSubmitted {
+            application_id: id,
+            applicant: &applicant,
+            requested_role: &application.requested_role,
+        }
+        .emit();
+        id
+    }
+
+    /// Nimmt einen Antrag direkt an (ManageRoles erforderlich)
+    pub fn approve_application(&mut self, application_id: u64) {
+        self.require_permission(&env::predecessor_account_id(), Permission::ManageRoles);
+        self.internal_approve_application(application_id);
+    }
+
+    /// Lehnt einen Antrag ab und erstattet eine hinterlegte Storage-Gebühr zurück
+    pub fn reject_application(&mut self, application_id: u64, reason: Option<String>) {
+        self.require_permission(&env::predecessor_account_id(), Permission::ManageRoles);
+        let mut application = self.internal_pending_application(application_id);
+        if application.storage_deposit.gt(&ZERO_TOKEN) {
+            Promise::new(application.applicant.clone())
+                .transfer(application.storage_deposit)
+                .detach();
+        }
+        application.status = ApplicationStatus::Rejected;
+        application.reason = reason;
+        self.internal_close_application(application);
+    }
+
+    pub fn get_application(&self, application_id: u64) -> Option<MembershipApplication> {
+        self.applications.get(&application_id)
+    }
+
+    /// Offene Anträge (paginiert)
+    pub fn get_pending_applications(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<MembershipApplication> {
+        self.pending_applications
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .filter_map(|id| self.applications.get(&id))
+            .collect()
+    }
+}
@@ -0,0 +1,320 @@
+// Versionierter Contract-State
+//
+// Der State liegt als Borsh-Struct unter dem Key "STATE". Jede Änderung am Layout von `Contract`
+// (neue Felder, geänderte Value-Typen in Collections) macht bestehenden State unlesbar. Deshalb:
+// - STATE_VERSION wird bei jeder Layout-Änderung erhöht und separat unter STATE_VERSION_KEY gespeichert
+// - das bisherige Layout wird hier als `ContractV<n>` eingefroren und bekommt eine `migrate`-Methode
+//   auf die nächste Version
+// - `VersionedContract` liest den State passend zur gespeicherten Version und migriert schrittweise
+//   bis zur aktuellen Version (aufgerufen von `Contract::migrate` nach einem Deployment)
+
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::{env, AccountId, NearToken, StorageUsage};
+
+use crate::config::DaoConfig;
+use crate::metadata::FungibleTokenMetadata;
+use crate::proposal::{Proposal, ProposalKind, ProposalStatus};
+use crate::roles::default_permission_matrix;
+use crate::*;
+
+/// Aktuelles Layout von `Contract`
+pub const STATE_VERSION: u32 = 2;
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
+// Feste Treasury- und Team-Accounts aus v1 (ab v2 in der DaoConfig)
+const V1_TREASURY_ACCOUNT: &str = "treasury.dao.lioneluser.testnet";
+const V1_TEAM_ACCOUNT: &str = "team.dao.lioneluser.testnet";
+
+/// Account-IDs aller Rollen-Einträge im v1-Layout, ermittelt aus den Keys eines State-Dumps
+/// (RPC `view_state`). v1 vergab Rollen auch an nicht registrierte Accounts; diese sind nur
+/// so auffindbar und müssen `migrate` übergeben werden.
+pub fn v1_role_holders<'a>(state_keys: impl IntoIterator<Item = &'a [u8]>) -> Vec<AccountId> {
+    let prefix = near_sdk::borsh::to_vec(&StorageKey::Roles).unwrap();
+    state_keys
+        .into_iter()
+        .filter_map(|key| key.strip_prefix(prefix.as_slice()))
+        .filter_map(|account_id| AccountId::try_from_slice(account_id).ok())
+        .collect()
+}
+
+/// Gespeicherte State-Version. State ohne Versions-Key stammt aus v1 (vor Einführung der Versionierung).
+pub(crate) fn read_state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|bytes| u32::try_from_slice(&bytes).unwrap_or_else(|_| DaoError::InvalidStateVersion.panic()))
+        .unwrap_or(1)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &near_sdk::borsh::to_vec(&STATE_VERSION).unwrap());
+}
+
+/// Proposal-Layout aus v1 (ohne `kind`)
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct ProposalV1 {
+    pub id: u64,
+    pub title: String,
+    pub description: String,
+    pub created_at: u64,
+    pub proposer: String,
+    pub executed: bool,
+    pub votes_for: Vec<(String, u128)>,
+    pub votes_against: Vec<(String, u128)>,
+    pub voted_accounts: Vec<String>,
+    pub status: ProposalStatus,
+    pub link: Option<String>,
+    pub tags: Vec<String>,
+    pub amount: Option<u128>,
+    pub target_account: Option<String>,
+    pub category: Option<String>,
+    pub deadline: Option<u64>,
+    pub required_role: Option<String>,
+    pub quorum: Option<u128>,
+}
+
+impl From<ProposalV1> for Proposal {
+    /// v1 kannte nur Transfer-Proposals (amount + target_account)
+    fn from(old: ProposalV1) -> Self {
+        Proposal {
+            id: old.id,
+            title: old.title,
+            description: old.description,
+            created_at: old.created_at,
+            proposer: old.proposer,
+            executed: old.executed,
+            votes_for: old.votes_for,
+            votes_against: old.votes_against,
+            voted_accounts: old.voted_accounts,
+            status: old.status,
+            link: old.link,
+            tags: old.tags,
+            amount: old.amount,
+            target_account: old.target_account,
+            category: old.category,
+            deadline: old.deadline,
+            required_role: old.required_role,
+            quorum: old.quorum,
+            kind: ProposalKind::Transfer,
+            // v1 hat Deposits direkt behalten, es gibt nichts zu erstatten
+            bond: U128(0),
+        }
+    }
+}
+
+/// Contract-Layout v1: eine Rolle pro Account, Treasury/Team als Konstanten
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct ContractV1 {
+    pub accounts: UnorderedMap<AccountId, NearToken>,
+    pub total_supply: NearToken,
+    pub bytes_for_longest_account_id: StorageUsage,
+    pub metadata: LazyOption<FungibleTokenMetadata>,
+    pub proposals: LookupMap<u64, ProposalV1>,
+    pub proposal_ids: Vector<u64>,
+    pub next_proposal_id: u64,
+    pub registered_accounts: Vector<AccountId>,
+    pub roles: LookupMap<AccountId, String>,
+    pub token_pool: NearToken,
+    pub community_treasury: NearToken,
+    pub team_tokens: NearToken,
+    pub team_accounts: Vector<AccountId>,
+}
+
+impl ContractV1 {
+    /// v1 -> v2: Proposals erhalten `kind`, Rollen werden zu RoleGrants (inkl. Core-Set und Historie),
+    /// neue Collections und die DaoConfig werden initialisiert, vorhandenes NEAR geht in den Treasury-Ledger.
+    /// Rollen werden für alle registrierten Accounts, Team-Accounts und `role_holders` übernommen.
+    /// Ein nicht übernommener v1-Eintrag bliebe als String unter dem Roles-Prefix liegen und wäre
+    /// im neuen Layout unlesbar.
+    pub fn migrate(mut self, role_holders: Vec<AccountId>) -> Contract {
+        let treasury_account_id: AccountId = V1_TREASURY_ACCOUNT.parse().unwrap();
+        let team_account_id: AccountId = V1_TEAM_ACCOUNT.parse().unwrap();
+
+        // Alte Werte lesen und entfernen, bevor die Prefixe mit den neuen Typen beschrieben werden
+        let proposals: Vec<ProposalV1> = self
+            .proposal_ids
+            .iter()
+            .filter_map(|id| self.proposals.remove(&id))
+            .collect();
+        let mut roles: Vec<(AccountId, String)> = vec![];
+        let accounts: Vec<AccountId> = self
+            .registered_accounts
+            .iter()
+            .chain(self.team_accounts.iter())
+            .chain(role_holders)
+            .collect();
+        for account_id in accounts {
+            if let Some(role) = self.roles.remove(&account_id) {
+                roles.push((account_id, role));
+            }
+        }
+
+        let mut this = Contract {
+            accounts: self.accounts,
+            total_supply: self.total_supply,
+            bytes_for_longest_account_id: self.bytes_for_longest_account_id,
+            metadata: self.metadata,
+            proposals: LookupMap::new(StorageKey::Proposals),
+            proposal_ids: self.proposal_ids,
+            next_proposal_id: self.next_proposal_id,
+            registered_accounts: self.registered_accounts,
+            roles: LookupMap::new(StorageKey::Roles),
+            token_pool: self.token_pool,
+            community_treasury: self.community_treasury,
+            team_tokens: self.team_tokens,
+            team_accounts: self.team_accounts,
+            role_permissions: UnorderedMap::new(StorageKey::RolePermissions),
+            core_members: UnorderedSet::new(StorageKey::CoreMembers),
+            role_history: Vector::new(StorageKey::RoleHistory),
+            applications: LookupMap::new(StorageKey::Applications),
+            pending_applications: UnorderedSet::new(StorageKey::PendingApplications),
+            application_by_account: LookupMap::new(StorageKey::ApplicationByAccount),
+            next_application_id: 0,
+            elections: Vector::new(StorageKey::Elections),
+            election_voters: LookupMap::new(StorageKey::ElectionVoters),
+            election_snapshots: LookupMap::new(StorageKey::ElectionSnapshots),
+            council: vec![],
+            config: DaoConfig::new(treasury_account_id, team_account_id),
+            treasury_holdings: UnorderedMap::new(StorageKey::TreasuryHoldings),
+            budgets: UnorderedMap::new(StorageKey::Budgets),
+            allowances: UnorderedMap::new(StorageKey::Allowances),
+            finance_spends: Vector::new(StorageKey::FinanceSpends),
+            pending_actions: UnorderedMap::new(StorageKey::PendingActions),
+            next_action_id: 0,
+            streams: UnorderedMap::new(StorageKey::Streams),
+            next_stream_id: 0,
+            grants: UnorderedMap::new(StorageKey::Grants),
+            next_grant_id: 0,
+            bounties: UnorderedMap::new(StorageKey::Bounties),
+            next_bounty_id: 0,
+            escrow_tokens: ZERO_TOKEN,
+            activity_log: LookupMap::new(StorageKey::ActivityLog),
+            activity_count: 0,
+            account_activity: LookupMap::new(StorageKey::AccountActivity),
+            paused: vec![],
+            upgrade_code: LookupMap::new(StorageKey::UpgradeCode),
+            upgrade: None,
+        };
+
+        for (role, permissions) in default_permission_matrix() {
+            this.role_permissions.insert(&role.to_string(), &permissions);
+        }
+        for proposal in proposals {
+            let proposal_id = proposal.id;
+            this.proposals.insert(&proposal_id, &proposal.into());
+        }
+        // Core zuerst, damit die Core-Invariante bei den übrigen Rollen erfüllt ist
+        roles.sort_by_key(|(_, role)| role != ROLE_CORE);
+        for (account_id, role) in roles {
+            this.internal_grant_role(&account_id, &role, None, Some("Migrated from state v1".to_string()));
+        }
+        // v1 hat NEAR (z.B. aus buy_tokens) ohne Ledger gehalten: alles ausser Storage-Reserve und
+        // dem Deposit dieses Aufrufs gehört der Treasury. Der Ledger-Eintrag wird vorher angelegt,
+        // damit die Reserve auch ihn abdeckt.
+        this.treasury_holdings.insert(&Asset::Near, &0);
+        let storage_reserve = env::storage_byte_cost().saturating_mul(env::storage_usage().into());
+        let held_near = env::account_balance()
+            .saturating_sub(storage_reserve)
+            .saturating_sub(env::attached_deposit());
+        this.internal_treasury_credit(&Asset::Near, held_near.as_yoctonear());
+        this
+    }
+}
+
+/// State in einer beliebigen bekannten Version
+pub enum VersionedContract {
+    V1(Box<ContractV1>),
+    V2(Box<Contract>),
+}
+
+impl VersionedContract {
+    /// Liest den gespeicherten State im Layout der gespeicherten Version
+    pub fn read() -> Self {
+        match read_state_version() {
+            1 => Self::V1(Box::new(read_state())),
+            2 => Self::V2(Box::new(read_state())),
+            version => DaoError::InvalidStateVersion.panic_with(version),
+        }
+    }
+
+    /// Migriert schrittweise bis zur aktuellen Version
+    pub fn migrate(self, role_holders: Vec<AccountId>) -> Contract {
+        match self {
+            Self::V1(old) => Self::V2(Box::new(old.migrate(role_holders))).migrate(vec![]),
+            Self::V2(current) => *current,
+        }
+    }
+}
+
+fn read_state<T: BorshDeserialize>() -> T {
+    env::state_read().unwrap_or_else(|| DaoError::StateMissing.panic())
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Wird nach jedem Deployment aufgerufen (apply_upgrade oder manuell vom Contract-Account)
+    /// und migriert den bestehenden State auf STATE_VERSION. Bei v1-State alle Rollen-Inhaber
+    /// übergeben, die weder registriert noch im Team sind (siehe `v1_role_holders`).
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(role_holders: Option<Vec<AccountId>>) -> Self {
+        let this = VersionedContract::read().migrate(role_holders.unwrap_or_default());
+        write_state_version();
+        this
+    }
+
+    pub fn get_state_version(&self) -> u32 {
+        read_state_version()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    /// v1-State mit dem Owner als einzigem Core-Mitglied; das Guthaben des Mock-Accounts
+    /// (1000 NEAR) stammt aus der Zeit vor dem Treasury-Ledger
+    fn v1_state() -> ContractV1 {
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        let mut v1 = ContractV1 {
+            accounts: UnorderedMap::new(StorageKey::Accounts),
+            total_supply: ZERO_TOKEN,
+            bytes_for_longest_account_id: 0,
+            metadata: LazyOption::new(StorageKey::Metadata, None),
+            proposals: LookupMap::new(StorageKey::Proposals),
+            proposal_ids: Vector::new(StorageKey::ProposalIds),
+            next_proposal_id: 0,
+            registered_accounts: Vector::new(b"r".to_vec()),
+            roles: LookupMap::new(StorageKey::Roles),
+            token_pool: ZERO_TOKEN,
+            community_treasury: ZERO_TOKEN,
+            team_tokens: ZERO_TOKEN,
+            team_accounts: Vector::new(b"t".to_vec()),
+        };
+        v1.accounts.insert(&owner(), &ZERO_TOKEN);
+        v1.registered_accounts.push(&owner());
+        v1.roles.insert(&owner(), &ROLE_CORE.to_string());
+        v1
+    }
+
+    #[test]
+    fn near_held_before_the_migration_can_be_spent() {
+        let mut contract = v1_state().migrate(vec![]);
+        let seeded = contract.internal_treasury_balance(&Asset::Near);
+        assert!(seeded > NearToken::from_near(900).as_yoctonear());
+        assert_eq!(contract.internal_spendable_near(), seeded);
+
+        let amount = NearToken::from_near(500).as_yoctonear();
+        let proposal_id = insert_accepted_proposal(
+            &mut contract,
+            ProposalKind::NearTransfer { receiver_id: account("carol"), amount: U128(amount) },
+            None,
+        );
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.execute_proposal(proposal_id);
+        assert_eq!(near_transfers(), vec![(account("carol"), amount)]);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), seeded - amount);
+    }
+}
@@ -0,0 +1,259 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Finanz-Aktionen, die bei aktivem Multisig M Freigaben benötigen
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, PartialEq, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum SensitiveAction {
+    ExecuteProposal { proposal_id: u64 },
+    /// Der Betrag wurde beim Einreichen angehängt und wird bis zur Ausführung gehalten
+    DistributeDividends { amount: U128 },
+    /// Notfall-Pause durch Core-Mitglieder (Quorum: DaoConfig::pause_quorum)
+    Pause { subsystem: Subsystem },
+}
+
+impl SensitiveAction {
+    fn required_permission(&self) -> Permission {
+        match self {
+            SensitiveAction::ExecuteProposal { .. } => Permission::Execute,
+            SensitiveAction::DistributeDividends { .. } => Permission::DistributeDividends,
+            SensitiveAction::Pause { .. } => Permission::Pause,
+        }
+    }
+
+    /// Anzahl benötigter Freigaben
+    fn threshold(&self, config: &DaoConfig) -> u32 {
+        match self {
+            SensitiveAction::Pause { .. } => config.pause_quorum,
+            _ => config.multisig_threshold,
+        }
+    }
+}
+
+/// Eintrag in der Freigabe-Warteschlange. `approvals` enthält (Account, Zeitpunkt in Sekunden).
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingAction {
+    pub id: u64,
+    pub action: SensitiveAction,
+    pub created_by: AccountId,
+    pub created_at: u64,
+    pub approvals: Vec<(AccountId, u64)>,
+}
+
+impl Contract {
+    /// Führt die Aktion direkt aus (Single-Signer) oder reiht sie als Freigabe in die Warteschlange ein
+    pub(crate) fn internal_submit_action(&mut self, action: SensitiveAction) {
+        if action.threshold(&self.config) <= 1 {
+            self.internal_run_action(action);
+            return;
+        }
+        // Dividenden bringen jeweils eigenes Geld mit und werden nie zusammengelegt
+        let existing = match action {
+            SensitiveAction::ExecuteProposal { .. } | SensitiveAction::Pause { .. } => self
+                .pending_actions
+                .values()
+                .find(|pending| pending.action == action)
+                .map(|pending| pending.id),
+            SensitiveAction::DistributeDividends { .. } => None,
+        };
+        let action_id = existing.unwrap_or_else(|| {
+            let id = self.next_action_id;
+            self.next_action_id += 1;
+            let pending = PendingAction {
+                id,
+                action,
+                created_by: env::predecessor_account_id(),
+                created_at: env::block_timestamp_ms() / 1000,
+                approvals: vec![],
+            };
+            self.pending_actions.insert(&id, &pending);
+            id
+        });
+        self.internal_approve_action(action_id);
+    }
+
+    fn internal_approve_action(&mut self, action_id: u64) {
+        let signer = env::predecessor_account_id();
+        let mut pending = self
+            .pending_actions
+            .get(&action_id)
+            .unwrap_or_else(|| DaoError::PendingActionNotFound.panic_with(action_id));
+        self.require_permission(&signer, pending.action.required_permission());
+        // Pausen werden vom Core-Quorum freigegeben, nicht vom Multisig-Signer-Set
+        let is_pause = matches!(pending.action, SensitiveAction::Pause { .. });
+        if !is_pause && !self.config.multisig_signers.is_empty() {
+            require!(
+                self.config.multisig_signers.contains(&signer),
+                DaoError::NotMultisigSigner.with_detail(&signer)
+            );
+        }
+
+        // Abgelaufene Freigaben verwerfen
+        let now = env::block_timestamp_ms() / 1000;
+        let ttl = self.config.approval_ttl_sec;
+        pending.approvals.retain(|(_, approved_at)| now < approved_at + ttl);
+        require!(
+            !pending.approvals.iter().any(|(account_id, _)| account_id == &signer),
+            DaoError::AlreadyApproved.to_string()
+        );
+        pending.approvals.push((signer.clone(), now));
+
+        let executed = pending.approvals.len() as u32 >= pending.action.threshold(&self.config);
+        ActionApproved {
+            action_id,
+            action: &pending.action,
+            signer: &signer,
+            approvals: pending.approvals.len() as u32,
+            executed,
+        }
+        .emit();
+        if executed {
+            self.pending_actions.remove(&action_id);
+            self.internal_run_action(pending.action);
+        } else {
+            self.pending_actions.insert(&action_id, &pending);
+        }
+    }
+
+    fn internal_run_action(&mut self, action: SensitiveAction) {
+        match action {
+            SensitiveAction::ExecuteProposal { proposal_id } => {
+                // Pause kann zwischen Einreichen und letzter Freigabe gesetzt worden sein
+                let proposal = self.assert_proposal_executable(proposal_id);
+                self.assert_governance_not_paused(&proposal);
+                self.internal_execute_proposal(proposal_id)
+            }
+            SensitiveAction::DistributeDividends { amount } => {
+                self.assert_not_paused(Subsystem::Dividends);
+                self.internal_distribute_dividends(amount.0)
+            }
+            SensitiveAction::Pause { subsystem } => self.internal_set_paused(subsystem, true),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Freigabe einer wartenden Aktion durch einen weiteren Signer
+    pub fn approve_action(&mut self, action_id: u64) {
+        self.internal_approve_action(action_id);
+    }
+
+    /// Zieht eine wartende Aktion zurück (nur Ersteller); angehängte Dividenden werden erstattet
+    pub fn cancel_action(&mut self, action_id: u64) {
+        let pending = self
+            .pending_actions
+            .get(&action_id)
+            .unwrap_or_else(|| DaoError::PendingActionNotFound.panic_with(action_id));
+        require!(
+            pending.created_by == env::predecessor_account_id(),
+            DaoError::NotActionCreator.to_string()
+        );
+        self.pending_actions.remove(&action_id);
+        ActionCancelled {
+            action_id,
+            action: &pending.action,
+            actor: &pending.created_by,
+        }
+        .emit();
+        if let SensitiveAction::DistributeDividends { amount } = pending.action {
+            if amount.0 > 0 {
+                Promise::new(pending.created_by)
+                    .transfer(NearToken::from_yoctonear(amount.0))
+                    .detach();
+            }
+        }
+    }
+
+    pub fn get_pending_actions(&self) -> Vec<PendingAction> {
+        self.pending_actions.values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_APPROVAL_TTL_SEC;
+    use crate::test_utils::*;
+
+    const DIVIDENDS: u128 = 10_000;
+
+    /// Multisig 2-of-N; Owner und Team-Account sind Core und dürfen Dividenden ausschütten
+    fn setup_multisig() -> Contract {
+        let mut contract = setup();
+        contract.config.multisig_threshold = 2;
+        contract
+    }
+
+    fn submit_dividends(contract: &mut Contract, now_ms: u64) -> u64 {
+        set_context(&owner(), NearToken::from_yoctonear(DIVIDENDS), now_ms);
+        contract.distribute_dividends();
+        contract.get_pending_actions()[0].id
+    }
+
+    #[test]
+    fn cancelling_pending_dividends_refunds_the_creator() {
+        let mut contract = setup_multisig();
+        let action_id = submit_dividends(&mut contract, START_MS);
+        assert!(near_transfers().is_empty());
+
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.cancel_action(action_id);
+        assert!(contract.get_pending_actions().is_empty());
+        assert_eq!(near_transfers(), vec![(owner(), DIVIDENDS)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "NotActionCreator")]
+    fn only_the_creator_can_cancel() {
+        let mut contract = setup_multisig();
+        let action_id = submit_dividends(&mut contract, START_MS);
+        set_context(&team(), ZERO_TOKEN, START_MS);
+        contract.cancel_action(action_id);
+    }
+
+    #[test]
+    fn second_approval_executes_the_action() {
+        let mut contract = setup_multisig();
+        let action_id = submit_dividends(&mut contract, START_MS);
+
+        set_context(&team(), ZERO_TOKEN, START_MS + 1_000);
+        contract.approve_action(action_id);
+        assert!(contract.get_pending_actions().is_empty());
+        let paid: u128 = near_transfers().iter().map(|(_, amount)| amount).sum();
+        assert!(paid > 0 && paid <= DIVIDENDS);
+    }
+
+    #[test]
+    fn expired_approvals_do_not_count() {
+        let mut contract = setup_multisig();
+        let action_id = submit_dividends(&mut contract, START_MS);
+
+        // Die Freigabe des Owners ist abgelaufen, die Aktion wartet weiter
+        let later = START_MS + DEFAULT_APPROVAL_TTL_SEC * 1000;
+        set_context(&team(), ZERO_TOKEN, later);
+        contract.approve_action(action_id);
+        let pending = contract.get_pending_actions();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].approvals, vec![(team(), later / 1000)]);
+        assert!(near_transfers().is_empty());
+
+        set_context(&owner(), ZERO_TOKEN, later + 1_000);
+        contract.approve_action(action_id);
+        assert!(contract.get_pending_actions().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "AlreadyApproved")]
+    fn signers_cannot_approve_twice() {
+        let mut contract = setup_multisig();
+        let action_id = submit_dividends(&mut contract, START_MS);
+        set_context(&owner(), ZERO_TOKEN, START_MS + 1_000);
+        contract.approve_action(action_id);
+    }
+}
@@ -0,0 +1,168 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::multisig::SensitiveAction;
+use crate::*;
+
+/// Teilbereiche, die im Notfall einzeln angehalten werden können
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+#[schemars(crate = "schemars")]
+pub enum Subsystem {
+    /// ft_transfer, ft_transfer_call, finance_spend
+    Transfers,
+    /// buy_tokens
+    Sale,
+    /// execute_proposal (ausser Unpause-Proposals)
+    GovernanceExecution,
+    /// distribute_dividends
+    Dividends,
+}
+
+impl Contract {
+    pub(crate) fn internal_is_paused(&self, subsystem: Subsystem) -> bool {
+        self.paused.contains(&subsystem)
+    }
+
+    pub(crate) fn assert_not_paused(&self, subsystem: Subsystem) {
+        if self.internal_is_paused(subsystem) {
+            DaoError::SubsystemPaused.panic_with(format!("{:?}", subsystem));
+        }
+    }
+
+    /// Unpause-Proposals bleiben ausführbar, auch wenn die Ausführung angehalten ist
+    pub(crate) fn assert_governance_not_paused(&self, proposal: &Proposal) {
+        if !matches!(proposal.kind, ProposalKind::Unpause { .. }) {
+            self.assert_not_paused(Subsystem::GovernanceExecution);
+        }
+    }
+
+    pub(crate) fn internal_set_paused(&mut self, subsystem: Subsystem, paused: bool) {
+        if paused == self.internal_is_paused(subsystem) {
+            return;
+        }
+        if paused {
+            self.paused.push(subsystem);
+        } else {
+            self.paused.retain(|s| *s != subsystem);
+        }
+        PauseChanged {
+            subsystem,
+            paused,
+            actor: &env::predecessor_account_id(),
+        }
+        .emit();
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Hält einen Teilbereich an. Guardians pausieren sofort, Accounts mit der Pause-Permission
+    /// benötigen gemeinsam `DaoConfig::pause_quorum` Freigaben (siehe approve_action).
+    /// Die Freigabe erfolgt nur per Unpause-Proposal.
+    pub fn pause(&mut self, subsystem: Subsystem) {
+        let caller = env::predecessor_account_id();
+        if self.config.guardians.contains(&caller) {
+            self.internal_set_paused(subsystem, true);
+            return;
+        }
+        self.require_permission(&caller, Permission::Pause);
+        self.internal_submit_action(SensitiveAction::Pause { subsystem });
+    }
+
+    /// Aktuell angehaltene Teilbereiche
+    pub fn paused(&self) -> Vec<Subsystem> {
+        self.paused.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigUpdate;
+    use crate::ft_core::FungibleTokenCore;
+    use crate::test_utils::*;
+
+    #[test]
+    #[should_panic(expected = "MissingPermission")]
+    fn pause_requires_the_pause_permission() {
+        let mut contract = setup();
+        contract.internal_register_account(&account("bob"));
+        contract.internal_grant_role(&account("bob"), ROLE_COMMUNITY, None, None);
+        set_context(&account("bob"), ZERO_TOKEN, START_MS);
+        contract.pause(Subsystem::Transfers);
+    }
+
+    fn paused(subsystem: Subsystem) -> Contract {
+        let mut contract = setup();
+        contract.config.guardians.push(account("guardian"));
+        set_context(&account("guardian"), ZERO_TOKEN, START_MS);
+        contract.pause(subsystem);
+        assert_eq!(contract.paused(), vec![subsystem]);
+        contract
+    }
+
+    fn execute_unpause(contract: &mut Contract, subsystem: Subsystem) {
+        let proposal_id = insert_accepted_proposal(contract, ProposalKind::Unpause { subsystem }, None);
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.execute_proposal(proposal_id);
+        assert!(contract.paused().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "SubsystemPaused")]
+    fn paused_transfers_reject_ft_transfer() {
+        let mut contract = paused(Subsystem::Transfers);
+        set_context(&team(), ONE_YOCTO, START_MS);
+        contract.ft_transfer(owner(), NearToken::from_yoctonear(ONE_TOKEN), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "SubsystemPaused")]
+    fn paused_sale_rejects_buy_tokens() {
+        let mut contract = paused(Subsystem::Sale);
+        set_context(&account("buyer"), NearToken::from_near(1), START_MS);
+        contract.buy_tokens();
+    }
+
+    #[test]
+    #[should_panic(expected = "SubsystemPaused")]
+    fn paused_governance_rejects_execute_proposal() {
+        let mut contract = paused(Subsystem::GovernanceExecution);
+        let update = ConfigUpdate { voting_period_sec: Some(86_400), ..Default::default() };
+        let proposal_id = insert_accepted_proposal(&mut contract, ProposalKind::UpdateConfig { update }, None);
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.execute_proposal(proposal_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "SubsystemPaused")]
+    fn paused_dividends_reject_distribute_dividends() {
+        let mut contract = paused(Subsystem::Dividends);
+        set_context(&owner(), NearToken::from_near(1), START_MS);
+        contract.distribute_dividends();
+    }
+
+    #[test]
+    fn unpause_proposal_reopens_transfers() {
+        let mut contract = paused(Subsystem::Transfers);
+        execute_unpause(&mut contract, Subsystem::Transfers);
+
+        set_context(&team(), ONE_YOCTO, START_MS);
+        contract.ft_transfer(owner(), NearToken::from_yoctonear(ONE_TOKEN), None);
+        assert_eq!(contract.ft_balance_of(owner()).as_yoctonear(), ONE_TOKEN);
+    }
+
+    #[test]
+    fn unpause_proposal_runs_while_governance_is_paused() {
+        let mut contract = paused(Subsystem::GovernanceExecution);
+        execute_unpause(&mut contract, Subsystem::GovernanceExecution);
+
+        let update = ConfigUpdate { voting_period_sec: Some(86_400), ..Default::default() };
+        let proposal_id = insert_accepted_proposal(&mut contract, ProposalKind::UpdateConfig { update }, None);
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.execute_proposal(proposal_id);
+        assert_eq!(contract.get_config().voting_period_sec, Some(86_400));
+    }
+}
@@ -2,6 +2,16 @@ use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
 
+use crate::bounties::bounty_total;
+use crate::config::ConfigUpdate;
+use crate::elections::VotingMode;
+use crate::grants::MilestoneSpec;
+use crate::pause::Subsystem;
+use crate::roles::Permission;
+use crate::treasury::Asset;
+use near_sdk::json_types::{Base58CryptoHash, U128};
+use near_sdk::{env, AccountId};
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, PartialEq, Debug, Clone)]
 #[serde(crate = "near_sdk::serde")]
 #[schemars(crate = "schemars")]
@@ -9,6 +19,111 @@ pub enum ProposalStatus {
     Open,
     Accepted,
     Rejected,
+    /// Asynchrone Auszahlung erfolgreich abgeschlossen
+    Executed,
+    /// Asynchrone Auszahlung fehlgeschlagen, Mittel wurden der Treasury wieder gutgeschrieben
+    Failed,
+}
+
+/// Was bei execute_proposal passiert. `Transfer` nutzt die Felder `amount` und `target_account`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, PartialEq, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[schemars(crate = "schemars")]
+pub enum ProposalKind {
+    Transfer,
+    SetRolePermissions { role: String, permissions: Vec<Permission> },
+    RemoveRole { role: String },
+    ApproveMembership { application_id: u64 },
+    /// Gewählte Core-Sitze gelten `term_ms` ab Ende der Abstimmung
+    StartElection { seats: u32, mode: VotingMode, nomination_period_ms: u64, voting_period_ms: u64, term_ms: u64 },
+    ChangeDaoAccounts { treasury_account: Option<String>, team_account: Option<String> },
+    UpdateConfig { update: ConfigUpdate },
+    /// Auszahlung von NEAR aus der Treasury
+    NearTransfer {
+        #[schemars(with = "String")]
+        receiver_id: AccountId,
+        #[schemars(with = "String")]
+        amount: U128,
+    },
+    /// Auszahlung eines beliebigen Treasury-Assets (NEAR oder NEP-141)
+    TreasuryTransfer {
+        asset: Asset,
+        #[schemars(with = "String")]
+        receiver_id: AccountId,
+        #[schemars(with = "String")]
+        amount: U128,
+    },
+    /// Legt ein Kategorie-Budget an oder ändert es (Periode in Sekunden)
+    SetBudget {
+        category: String,
+        asset: Asset,
+        #[schemars(with = "String")]
+        amount: U128,
+        period_sec: u64,
+        rollover: bool,
+    },
+    RemoveBudget { category: String, asset: Asset },
+    /// Ausgabenlimit für ein Finance-Mitglied (finance_spend), Periode in Sekunden
+    SetAllowance {
+        #[schemars(with = "String")]
+        account_id: AccountId,
+        #[schemars(with = "String")]
+        per_tx_max: U128,
+        #[schemars(with = "String")]
+        per_period_max: U128,
+        period_sec: u64,
+        #[schemars(with = "Option<Vec<String>>")]
+        allowed_recipients: Option<Vec<AccountId>>,
+    },
+    RevokeAllowance {
+        #[schemars(with = "String")]
+        account_id: AccountId,
+    },
+    /// Zahlungsstrom aus der Treasury (Zeiten in Sekunden, `cliff` standardmässig = `start`)
+    CreateStream {
+        #[schemars(with = "String")]
+        recipient: AccountId,
+        asset: Asset,
+        #[schemars(with = "String")]
+        rate_per_sec: U128,
+        start: u64,
+        end: u64,
+        cliff: Option<u64>,
+    },
+    CancelStream { stream_id: u64 },
+    /// Grant mit Tranchen; der Gesamtbetrag wird bei der Ausführung reserviert
+    CreateGrant {
+        #[schemars(with = "String")]
+        grantee: AccountId,
+        asset: Asset,
+        milestones: Vec<MilestoneSpec>,
+    },
+    /// Freigabe einer eingereichten Tranche per Abstimmung (statt durch einen Reviewer)
+    ReleaseMilestone { grant_id: u64, milestone_index: u32 },
+    CancelGrant { grant_id: u64 },
+    /// Bounty mit `max_claims` Plätzen zu je `amount`; Zeiten in Sekunden
+    CreateBounty {
+        description: String,
+        asset: Asset,
+        #[schemars(with = "String")]
+        amount: U128,
+        max_claims: u32,
+        deadline: u64,
+        claim_period_sec: u64,
+    },
+    ApproveBountyClaim {
+        bounty_id: u64,
+        #[schemars(with = "String")]
+        claimant: AccountId,
+    },
+    CancelBounty { bounty_id: u64 },
+    /// Hebt eine Notfall-Pause auf (auch ausführbar, wenn GovernanceExecution pausiert ist)
+    Unpause { subsystem: Subsystem },
+    /// Contract-Upgrade auf vorab per stage_upgrade_code hinterlegten Code (sha256, base58)
+    UpgradeContract {
+        #[schemars(with = "String")]
+        code_hash: Base58CryptoHash,
+    },
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, Debug)]
@@ -33,4 +148,35 @@ pub struct Proposal {
     pub deadline: Option<u64>,
     pub required_role: Option<String>,
     pub quorum: Option<u128>,
-}
\ No newline at end of file
+    pub kind: ProposalKind,
+    /// Hinterlegter Proposal-Bond (yoctoNEAR), wird bei der Finalisierung erstattet oder verfällt
+    #[schemars(with = "String")]
+    pub bond: U128,
+}
+
+impl Proposal {
+    /// Auszahlung per Promise, deren Ergebnis erst im Callback feststeht
+    pub fn has_async_payout(&self) -> bool {
+        matches!(self.kind, ProposalKind::NearTransfer { .. } | ProposalKind::TreasuryTransfer { .. })
+    }
+
+    /// Asset und Betrag, die bei der Ausführung die Treasury verlassen (für Budget-Prüfungen).
+    /// DAO-Tokens werden als NEP-141 Asset dieses Contracts geführt.
+    pub fn payout(&self) -> Option<(Asset, u128)> {
+        match &self.kind {
+            ProposalKind::Transfer => self
+                .amount
+                .map(|amount| (Asset::Ft { token_id: env::current_account_id() }, amount)),
+            ProposalKind::NearTransfer { amount, .. } => Some((Asset::Near, amount.0)),
+            ProposalKind::TreasuryTransfer { asset, amount, .. } => Some((asset.clone(), amount.0)),
+            ProposalKind::CreateGrant { asset, milestones, .. } => Some((
+                asset.clone(),
+                milestones.iter().map(|milestone| milestone.amount.0).sum(),
+            )),
+            ProposalKind::CreateBounty { asset, amount, max_claims, .. } => {
+                Some((asset.clone(), bounty_total(*amount, *max_claims)))
+            }
+            _ => None,
+        }
+    }
+}
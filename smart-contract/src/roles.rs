@@ -0,0 +1,360 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::*;
+
+/// Einzelne Berechtigungen, die einer Rolle über die Permission-Matrix zugeordnet werden.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+#[schemars(crate = "schemars")]
+pub enum Permission {
+    CreateProposal,
+    Vote,
+    Finalize,
+    Execute,
+    ManageRoles,
+    ManageTeam,
+    DistributeDividends,
+    /// Freigabe von Grant-Meilensteinen ohne Abstimmung
+    ReviewGrants,
+    /// Genehmigung von Bounty-Einreichungen ohne Abstimmung
+    ReviewBounties,
+    /// Notfall-Pause (zusammen mit weiteren Core-Mitgliedern, siehe DaoConfig::pause_quorum)
+    Pause,
+}
+
+/// Standard-Matrix beim Deployment – entspricht den bisher fest codierten Rollen-Checks.
+pub(crate) fn default_permission_matrix() -> Vec<(&'static str, Vec<Permission>)> {
+    use Permission::*;
+    vec![
+        (
+            ROLE_CORE,
+            vec![CreateProposal, Vote, Finalize, Execute, ManageRoles, ManageTeam, DistributeDividends, ReviewGrants, ReviewBounties, Pause],
+        ),
+        (ROLE_FINANCE, vec![Vote, Finalize, Execute, DistributeDividends]),
+        (ROLE_COMMUNITY, vec![CreateProposal, Vote]),
+        (ROLE_VISITOR, vec![]),
+    ]
+}
+
+/// Eine einzelne Rollen-Zuweisung; `expires_at` (ms) begrenzt gewählte Amtszeiten.
+/// `auto` markiert Rollen, die über die Token-Schwellen (DaoConfig::role_thresholds) vergeben wurden.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleGrant {
+    pub role: String,
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub auto: bool,
+}
+
+impl RoleGrant {
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        self.expires_at.is_none_or(|expires_at| now_ms < expires_at)
+    }
+}
+
+/// Protokoll-Eintrag für jede Rollenänderung (wer, was, warum)
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleChange {
+    pub account_id: AccountId,
+    pub role: String,
+    pub granted: bool,
+    pub expires_at: Option<u64>,
+    pub actor: AccountId,
+    pub reason: Option<String>,
+    pub timestamp: u64,
+}
+
+/// Reihenfolge, nach der die "Haupt-Rolle" eines Accounts bestimmt wird (für get_role / get_all_roles).
+const ROLE_PRECEDENCE: [&str; 4] = [ROLE_CORE, ROLE_FINANCE, ROLE_COMMUNITY, ROLE_VISITOR];
+
+impl Contract {
+    /// Aktive (nicht abgelaufene) Rollen eines Accounts – wird bei jedem Permission-Check ausgewertet.
+    pub(crate) fn internal_roles_of(&self, account_id: &AccountId) -> Vec<String> {
+        let now = env::block_timestamp_ms();
+        self.roles
+            .get(account_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|grant| grant.is_active(now))
+            .map(|grant| grant.role)
+            .collect()
+    }
+
+    pub(crate) fn internal_has_role(&self, account_id: &AccountId, role: &str) -> bool {
+        self.internal_roles_of(account_id).iter().any(|r| r == role)
+    }
+
+    /// Fügt eine Rolle zum Rollen-Set hinzu (oder erneuert deren Ablaufzeit).
+    /// Sobald ein Account eine echte Rolle hat, entfällt "visitor"; "visitor" selbst setzt den Account zurück.
+    pub(crate) fn internal_grant_role(
+        &mut self,
+        account_id: &AccountId,
+        role: &str,
+        expires_at: Option<u64>,
+        reason: Option<String>,
+    ) {
+        if role == ROLE_VISITOR {
+            for grant in self.roles.get(account_id).unwrap_or_default() {
+                if grant.role != ROLE_VISITOR {
+                    self.internal_revoke_role(account_id, &grant.role, reason.clone());
+                }
+            }
+            if self.roles.get(account_id).unwrap_or_default().is_empty() {
+                self.roles.insert(account_id, &vec![RoleGrant { role: ROLE_VISITOR.to_string(), expires_at: None, auto: false }]);
+            }
+            return;
+        }
+        if let Some(expires_at) = expires_at {
+            require!(expires_at > env::block_timestamp_ms(), DaoError::RoleExpiryInPast.to_string());
+        }
+        let grant = RoleGrant { role: role.to_string(), expires_at, auto: false };
+        self.internal_insert_grant(account_id, grant, reason);
+    }
+
+    fn internal_insert_grant(&mut self, account_id: &AccountId, grant: RoleGrant, reason: Option<String>) {
+        let (role, expires_at, auto) = (grant.role.clone(), grant.expires_at, grant.auto);
+        let mut grants = self.roles.get(account_id).unwrap_or_default();
+        grants.retain(|g| g.role != ROLE_VISITOR && g.role != role);
+        grants.push(grant);
+        self.roles.insert(account_id, &grants);
+
+        if role == ROLE_CORE {
+            self.core_members.insert(account_id);
+            self.assert_core_invariant();
+        }
+        self.internal_log_role_change(account_id, &role, true, expires_at, auto, reason);
+    }
+
+    /// Automatische Rollen-Stufen anhand des Token-Guthabens (aufgerufen von internal_deposit/internal_withdraw).
+    /// Accounts mit manuell vergebenen Rollen (z.B. core, finance) sind davon ausgenommen.
+    pub(crate) fn internal_update_role_tiers(&mut self, account_id: &AccountId) {
+        let now = env::block_timestamp_ms();
+        let grants = self.roles.get(account_id).unwrap_or_default();
+        let has_manual_role = grants
+            .iter()
+            .any(|grant| !grant.auto && grant.role != ROLE_VISITOR && grant.is_active(now));
+        if has_manual_role {
+            return;
+        }
+
+        let balance = self.accounts.get(account_id).unwrap_or(ZERO_TOKEN).as_yoctonear();
+        for (role, U128(min_balance)) in self.config.role_thresholds.clone() {
+            let holds_role = grants.iter().any(|grant| grant.role == role);
+            if balance >= min_balance && !holds_role {
+                let grant = RoleGrant { role, expires_at: None, auto: true };
+                self.internal_insert_grant(account_id, grant, Some("Balance threshold reached".to_string()));
+            } else if balance < min_balance && holds_role {
+                self.internal_revoke_role(account_id, &role, Some("Balance below threshold".to_string()));
+            }
+        }
+    }
+
+    pub(crate) fn assert_valid_role_thresholds(&self, thresholds: &[(String, U128)]) {
+        for (role, _) in thresholds.iter() {
+            require!(
+                role != ROLE_VISITOR && role != ROLE_CORE && self.role_permissions.get(role).is_some(),
+                DaoError::InvalidThresholdRole.with_detail(role)
+            );
+        }
+    }
+
+    /// Entfernt eine Rolle. Bleibt keine Rolle übrig, fällt der Account auf "visitor" zurück.
+    pub(crate) fn internal_revoke_role(&mut self, account_id: &AccountId, role: &str, reason: Option<String>) {
+        require!(role != ROLE_VISITOR, DaoError::VisitorRoleImmutable.to_string());
+        let mut grants = self.roles.get(account_id).unwrap_or_default();
+        let before = grants.len();
+        let auto = grants.iter().any(|grant| grant.role == role && grant.auto);
+        grants.retain(|grant| grant.role != role);
+        require!(grants.len() < before, DaoError::RoleNotHeld.with_detail(role));
+        if grants.is_empty() {
+            grants.push(RoleGrant { role: ROLE_VISITOR.to_string(), expires_at: None, auto: false });
+        }
+        self.roles.insert(account_id, &grants);
+
+        if role == ROLE_CORE {
+            self.core_members.remove(account_id);
+            self.assert_core_invariant();
+        }
+        self.internal_log_role_change(account_id, role, false, None, auto, reason);
+    }
+
+    /// Invariante: mindestens ein Core-Mitglied ohne Ablaufdatum muss immer existieren.
+    pub(crate) fn assert_core_invariant(&self) {
+        require!(
+            self.core_members.iter().any(|account_id| {
+                self.roles
+                    .get(&account_id)
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|grant| grant.role == ROLE_CORE && grant.expires_at.is_none())
+            }),
+            DaoError::PermanentCoreRequired.to_string()
+        );
+    }
+
+    /// Automatische Stufenwechsel (`auto`) kann jeder Transfer auslösen; sie landen daher nur im
+    /// begrenzten Aktivitätsprotokoll und nicht in der unbegrenzten role_history.
+    fn internal_log_role_change(
+        &mut self,
+        account_id: &AccountId,
+        role: &str,
+        granted: bool,
+        expires_at: Option<u64>,
+        auto: bool,
+        reason: Option<String>,
+    ) {
+        let change = RoleChange {
+            account_id: account_id.clone(),
+            role: role.to_string(),
+            granted,
+            expires_at,
+            actor: env::predecessor_account_id(),
+            reason,
+            timestamp: env::block_timestamp_ms(),
+        };
+        RoleChanged { change: &change }.emit();
+        self.internal_record_activity(
+            ActivityKind::RoleChange,
+            account_id,
+            Some(&change.actor),
+            None,
+            Some(format!("{}{}", if granted { "+" } else { "-" }, change.role)),
+        );
+        if !auto {
+            self.role_history.push(&change);
+        }
+    }
+
+    pub(crate) fn internal_has_permission(&self, account_id: &AccountId, permission: Permission) -> bool {
+        self.internal_roles_of(account_id).iter().any(|role| {
+            self.role_permissions
+                .get(role)
+                .is_some_and(|permissions| permissions.contains(&permission))
+        })
+    }
+
+    /// Zentraler Berechtigungs-Check für alle geschützten Methoden.
+    pub(crate) fn require_permission(&self, account_id: &AccountId, permission: Permission) {
+        if !self.internal_has_permission(account_id, permission) {
+            DaoError::MissingPermission.panic_with(format!("{} lacks {:?}", account_id, permission));
+        }
+    }
+
+    pub(crate) fn internal_set_role_permissions(&mut self, role: &str, permissions: Vec<Permission>) {
+        self.role_permissions.insert(&role.to_string(), &permissions);
+        self.assert_role_manager_exists();
+    }
+
+    pub(crate) fn internal_remove_role_definition(&mut self, role: &str) {
+        require!(role != ROLE_VISITOR, DaoError::VisitorRoleImmutable.to_string());
+        require!(
+            self.role_permissions.remove(&role.to_string()).is_some(),
+            DaoError::InvalidRole.with_detail(role)
+        );
+        self.assert_role_manager_exists();
+    }
+
+    fn assert_role_manager_exists(&self) {
+        require!(
+            self.role_permissions
+                .values()
+                .any(|permissions| permissions.contains(&Permission::ManageRoles)),
+            DaoError::RoleManagerRequired.to_string()
+        );
+    }
+
+    pub(crate) fn internal_primary_role(&self, account_id: &AccountId) -> Option<String> {
+        self.roles.get(account_id)?;
+        let roles = self.internal_roles_of(account_id);
+        ROLE_PRECEDENCE
+            .iter()
+            .find(|role| roles.iter().any(|r| r == *role))
+            .map(|role| role.to_string())
+            .or_else(|| roles.first().cloned())
+            .or_else(|| Some(ROLE_VISITOR.to_string()))
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Alle Rollen eines Accounts
+    pub fn get_roles(&self, account_id: AccountId) -> Vec<String> {
+        self.internal_roles_of(&account_id)
+    }
+
+    /// Rollen inklusive Ablaufzeitpunkt (auch bereits abgelaufene Amtszeiten)
+    pub fn get_role_grants(&self, account_id: AccountId) -> Vec<RoleGrant> {
+        self.roles.get(&account_id).unwrap_or_default()
+    }
+
+    /// Entzieht einem Account eine Rolle (ManageRoles erforderlich)
+    #[payable]
+    pub fn revoke_role(&mut self, account_id: AccountId, role: String, reason: Option<String>) {
+        self.require_permission(&env::predecessor_account_id(), Permission::ManageRoles);
+        self.internal_revoke_role(&account_id, &role, reason);
+    }
+
+    /// Protokoll aller Rollenänderungen (paginiert)
+    pub fn get_role_history(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<RoleChange> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(50);
+        (from_index..std::cmp::min(from_index.saturating_add(limit), self.role_history.len()))
+            .filter_map(|index| self.role_history.get(index))
+            .collect()
+    }
+
+    /// Token-Schwellen für automatische Rollen (Rolle, Mindestguthaben in Yocto)
+    pub fn get_role_thresholds(&self) -> Vec<(String, U128)> {
+        self.config.role_thresholds.clone()
+    }
+
+    /// Aktuelle Permission-Matrix (Rolle -> Berechtigungen)
+    pub fn get_permission_matrix(&self) -> Vec<(String, Vec<Permission>)> {
+        self.role_permissions.iter().collect()
+    }
+
+    pub fn has_permission(&self, account_id: AccountId, permission: Permission) -> bool {
+        self.internal_has_permission(&account_id, permission)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn automatic_tier_changes_do_not_grow_the_role_history() {
+        let mut contract = setup();
+        contract.config.role_thresholds = vec![(ROLE_COMMUNITY.to_string(), U128(10 * ONE_TOKEN))];
+        let bob = account("bob");
+        contract.internal_register_account(&bob);
+        let history = contract.role_history.len();
+        let events = event_count("role_changed");
+
+        for _ in 0..3 {
+            fund(&mut contract, &bob, 10);
+            assert!(contract.internal_has_role(&bob, ROLE_COMMUNITY));
+            contract.internal_transfer(&bob, &treasury(), NearToken::from_yoctonear(10 * ONE_TOKEN), None);
+            assert!(!contract.internal_has_role(&bob, ROLE_COMMUNITY));
+        }
+        assert_eq!(contract.role_history.len(), history);
+        // Events und Aktivitätsprotokoll erfassen die Wechsel weiterhin
+        assert_eq!(event_count("role_changed"), events + 6);
+    }
+
+    #[test]
+    fn manual_role_changes_are_kept_in_the_role_history() {
+        let mut contract = setup();
+        let history = contract.role_history.len();
+        contract.internal_grant_role(&account("bob"), ROLE_FINANCE, None, Some("Elected".to_string()));
+        contract.internal_revoke_role(&account("bob"), ROLE_FINANCE, None);
+        assert_eq!(contract.role_history.len(), history + 2);
+    }
+}
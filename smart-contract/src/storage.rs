@@ -2,6 +2,7 @@ use near_sdk::{env, log, AccountId, Promise};
 use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
 use near_sdk::serde::{Deserialize, Serialize};
 
+use crate::activity::ACCOUNT_ACTIVITY_STORAGE_BYTES;
 use crate::*;
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema)]
@@ -46,19 +47,19 @@ impl StorageManagement for Contract {
         if self.accounts.get(&account_id).is_some() {
             log!("The account is already registered, refunding the deposit");
             if amount.gt(&ZERO_TOKEN) {
-                Promise::new(env::predecessor_account_id()).transfer(amount);
+                Promise::new(env::predecessor_account_id()).transfer(amount).detach();
             }
         } else {
             let min_balance = self.storage_balance_bounds().min;
             if amount < min_balance {
-                env::panic_str("The attached deposit is less than the minimum storage balance");
+                DaoError::InsufficientStorageDeposit.panic_with(min_balance.as_yoctonear());
             }
 
             self.internal_register_account(&account_id);
 
             let refund = amount.saturating_sub(min_balance);
             if refund.gt(&ZERO_TOKEN) {
-                Promise::new(env::predecessor_account_id()).transfer(refund);
+                Promise::new(env::predecessor_account_id()).transfer(refund).detach();
             }
         }
 
@@ -69,8 +70,9 @@ impl StorageManagement for Contract {
     }
 
     fn storage_balance_bounds(&self) -> StorageBalanceBounds {
-        let required_storage_balance =
-            env::storage_byte_cost().saturating_mul(self.bytes_for_longest_account_id.into());
+        // Account-Eintrag plus voller Aktivitäts-Index (siehe activity.rs)
+        let required_storage_balance = env::storage_byte_cost()
+            .saturating_mul((self.bytes_for_longest_account_id + ACCOUNT_ACTIVITY_STORAGE_BYTES).into());
 
         StorageBalanceBounds {
             min: required_storage_balance,
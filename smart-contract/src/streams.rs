@@ -0,0 +1,325 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::Gas;
+
+use crate::treasury::{is_promise_success, Asset};
+use crate::*;
+
+const GAS_FOR_STREAM_CALLBACK: Gas = Gas::from_tgas(10);
+
+/// Zahlungsstrom (Lohn, Grants), beim Erstellen vollständig aus der Treasury reserviert.
+/// Zeitangaben in Sekunden; vor `cliff` ist nichts auszahlbar.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaymentStream {
+    pub id: u64,
+    pub recipient: AccountId,
+    pub asset: Asset,
+    pub rate_per_sec: U128,
+    pub start: u64,
+    pub end: u64,
+    pub cliff: u64,
+    pub withdrawn: U128,
+    pub cancelled_at: Option<u64>,
+}
+
+impl PaymentStream {
+    pub fn total(&self) -> u128 {
+        self.rate_per_sec.0 * (self.end - self.start) as u128
+    }
+
+    /// Bis `now` freigegebener Betrag (inkl. bereits ausgezahlter Beträge)
+    pub fn vested(&self, now: u64) -> u128 {
+        let until = now.min(self.end).min(self.cancelled_at.unwrap_or(self.end));
+        if until < self.cliff {
+            return 0;
+        }
+        self.rate_per_sec.0 * until.saturating_sub(self.start) as u128
+    }
+
+    pub fn owed(&self, now: u64) -> u128 {
+        self.vested(now).saturating_sub(self.withdrawn.0)
+    }
+
+    pub fn is_active(&self, now: u64) -> bool {
+        self.cancelled_at.is_none() && now < self.end
+    }
+}
+
+fn now_sec() -> u64 {
+    env::block_timestamp_ms() / 1000
+}
+
+impl Contract {
+    pub(crate) fn internal_create_stream(
+        &mut self,
+        recipient: AccountId,
+        asset: Asset,
+        rate_per_sec: U128,
+        start: u64,
+        end: u64,
+        cliff: Option<u64>,
+    ) {
+        let cliff = cliff.unwrap_or(start);
+        require!(rate_per_sec.0 > 0, DaoError::InvalidStreamRate.to_string());
+        require!(start < end, DaoError::InvalidStreamSchedule.to_string());
+        require!(start <= cliff && cliff <= end, DaoError::InvalidStreamSchedule.to_string());
+        // total() und vested() rechnen danach ungeprüft, vested ist nie grösser als total
+        require!(
+            rate_per_sec.0.checked_mul((end - start) as u128).is_some(),
+            DaoError::StreamTotalOverflow.to_string()
+        );
+
+        let id = self.next_stream_id;
+        let stream = PaymentStream {
+            id,
+            recipient,
+            asset,
+            rate_per_sec,
+            start,
+            end,
+            cliff,
+            withdrawn: U128(0),
+            cancelled_at: None,
+        };
+
+        // Gesamtbetrag reservieren
+        self.internal_reserve_from_treasury(&stream.asset, stream.total(), format!("Stream #{} funding", id));
+
+        self.streams.insert(&id, &stream);
+        self.next_stream_id += 1;
+        StreamCreated { stream: &stream }.emit();
+    }
+
+    /// Bricht einen Stream ab; der noch nicht freigegebene Rest geht an die Treasury zurück
+    pub(crate) fn internal_cancel_stream(&mut self, stream_id: u64) {
+        let mut stream = self
+            .streams
+            .get(&stream_id)
+            .unwrap_or_else(|| DaoError::StreamNotFound.panic_with(stream_id));
+        let now = now_sec();
+        require!(stream.is_active(now), DaoError::StreamNotActive.with_detail(stream_id));
+
+        stream.cancelled_at = Some(std::cmp::max(now, stream.start));
+        let remainder = stream.total() - stream.vested(stream.end);
+        if remainder > 0 {
+            self.internal_return_to_treasury(&stream.asset, remainder, format!("Stream #{} cancelled", stream_id));
+        }
+        self.streams.insert(&stream_id, &stream);
+        StreamCancelled {
+            stream_id,
+            cancelled_at: std::cmp::max(now, stream.start),
+            refunded: U128(remainder),
+        }
+        .emit();
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Zahlt dem Empfänger alle bis jetzt freigegebenen Beträge aus
+    pub fn withdraw_stream(&mut self, stream_id: u64) -> U128 {
+        let mut stream = self
+            .streams
+            .get(&stream_id)
+            .unwrap_or_else(|| DaoError::StreamNotFound.panic_with(stream_id));
+        require!(
+            env::predecessor_account_id() == stream.recipient,
+            DaoError::NotStreamRecipient.to_string()
+        );
+        let amount = stream.owed(now_sec());
+        require!(amount > 0, DaoError::NothingToWithdraw.to_string());
+        stream.withdrawn = U128(stream.withdrawn.0 + amount);
+        self.streams.insert(&stream_id, &stream);
+
+        let memo = format!("Stream #{}", stream_id);
+        if let Some(transfer) = self.internal_pay_reserved(&stream.asset, &stream.recipient, amount, memo) {
+            transfer
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_STREAM_CALLBACK)
+                        .on_stream_withdraw(stream_id, U128(amount)),
+                )
+                .detach();
+        } else {
+            StreamWithdrawn {
+                stream_id,
+                recipient: &stream.recipient,
+                amount: U128(amount),
+            }
+            .emit();
+        }
+        U128(amount)
+    }
+
+    /// Meldet die Auszahlung bzw. setzt den ausgezahlten Betrag zurück, falls die Überweisung
+    /// fehlgeschlagen ist
+    #[private]
+    pub fn on_stream_withdraw(&mut self, stream_id: u64, amount: U128) -> bool {
+        let succeeded = is_promise_success();
+        if let Some(mut stream) = self.streams.get(&stream_id) {
+            if succeeded {
+                StreamWithdrawn {
+                    stream_id,
+                    recipient: &stream.recipient,
+                    amount,
+                }
+                .emit();
+            } else {
+                stream.withdrawn = U128(stream.withdrawn.0.saturating_sub(amount.0));
+                self.streams.insert(&stream_id, &stream);
+            }
+        }
+        if !succeeded {
+            env::log_str(&format!("Withdrawal from stream #{} failed", stream_id));
+        }
+        succeeded
+    }
+
+    pub fn get_stream(&self, stream_id: u64) -> Option<PaymentStream> {
+        self.streams.get(&stream_id)
+    }
+
+    /// Laufende Streams (weder beendet noch abgebrochen)
+    pub fn get_active_streams(&self) -> Vec<PaymentStream> {
+        let now = now_sec();
+        self.streams.values().filter(|stream| stream.is_active(now)).collect()
+    }
+
+    /// Aktuell auszahlbarer Betrag eines Streams
+    pub fn get_stream_owed(&self, stream_id: u64) -> U128 {
+        U128(self.streams.get(&stream_id).map_or(0, |stream| stream.owed(now_sec())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ft_core::FungibleTokenCore;
+    use crate::test_utils::*;
+
+    const START: u64 = START_MS / 1000;
+    const RATE: u128 = 1_000;
+
+    fn dao_token() -> Asset {
+        Asset::Ft { token_id: dao() }
+    }
+
+    fn token_balance(contract: &Contract, account_id: &AccountId) -> u128 {
+        contract.accounts.get(account_id).unwrap_or(ZERO_TOKEN).as_yoctonear()
+    }
+
+    /// Stream über 100 Sekunden mit Cliff nach 20 Sekunden
+    fn setup_stream(asset: Asset) -> Contract {
+        let mut contract = setup();
+        if asset == Asset::Near {
+            set_context(&account("donor"), NearToken::from_near(10), START_MS);
+            contract.donate();
+        }
+        contract.internal_create_stream(account("bob"), asset, U128(RATE), START, START + 100, Some(START + 20));
+        contract
+    }
+
+    fn withdraw(contract: &mut Contract, now: u64) -> u128 {
+        set_context(&account("bob"), ZERO_TOKEN, now * 1000);
+        contract.withdraw_stream(0).0
+    }
+
+    #[test]
+    fn vesting_is_linear_after_the_cliff() {
+        let contract = setup_stream(dao_token());
+        let stream = contract.get_stream(0).unwrap();
+        assert_eq!(stream.total(), 100 * RATE);
+        assert_eq!(stream.vested(START + 19), 0);
+        assert_eq!(stream.vested(START + 20), 20 * RATE);
+        assert_eq!(stream.vested(START + 50), 50 * RATE);
+        assert_eq!(stream.vested(START + 500), 100 * RATE);
+    }
+
+    #[test]
+    fn creating_a_token_stream_moves_the_total_into_escrow() {
+        let contract = setup_stream(dao_token());
+        assert_eq!(token_balance(&contract, &treasury()), 3_000_000 * ONE_TOKEN - 100 * RATE);
+        assert_eq!(contract.escrow_tokens.as_yoctonear(), 100 * RATE);
+        assert_eq!(contract.ft_balance_of(dao()).as_yoctonear(), 100 * RATE);
+    }
+
+    #[test]
+    fn creating_a_near_stream_reserves_the_total() {
+        let contract = setup_stream(Asset::Near);
+        let donated = NearToken::from_near(10).as_yoctonear();
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), donated - 100 * RATE);
+    }
+
+    #[test]
+    fn withdrawals_pay_only_the_owed_amount() {
+        let mut contract = setup_stream(dao_token());
+        assert_eq!(withdraw(&mut contract, START + 30), 30 * RATE);
+        assert_eq!(event_count("stream_withdrawn"), 1);
+        assert_eq!(withdraw(&mut contract, START + 45), 15 * RATE);
+        assert_eq!(token_balance(&contract, &account("bob")), 45 * RATE);
+        assert_eq!(contract.get_stream_owed(0), U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "NothingToWithdraw")]
+    fn nothing_is_owed_before_the_cliff() {
+        let mut contract = setup_stream(dao_token());
+        withdraw(&mut contract, START + 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "StreamTotalOverflow")]
+    fn overflowing_total_is_rejected() {
+        let mut contract = setup();
+        contract.internal_create_stream(account("bob"), dao_token(), U128(u128::MAX / 2), START, START + 3, None);
+    }
+
+    #[test]
+    fn cancel_returns_the_unvested_remainder() {
+        let mut contract = setup_stream(dao_token());
+        let treasury_after_funding = token_balance(&contract, &treasury());
+        withdraw(&mut contract, START + 30);
+
+        set_context(&owner(), ZERO_TOKEN, (START + 40) * 1000);
+        contract.internal_cancel_stream(0);
+        assert_eq!(event_count("stream_cancelled"), 1);
+        assert_eq!(token_balance(&contract, &treasury()), treasury_after_funding + 60 * RATE);
+        assert!(contract.get_active_streams().is_empty());
+
+        // Bereits freigegebene Beträge bleiben auszahlbar, danach kommt nichts mehr hinzu
+        assert_eq!(withdraw(&mut contract, START + 90), 10 * RATE);
+        assert_eq!(token_balance(&contract, &account("bob")), 40 * RATE);
+        assert_eq!(contract.escrow_tokens, ZERO_TOKEN);
+    }
+
+    #[test]
+    fn cancel_before_the_cliff_returns_everything() {
+        let mut contract = setup_stream(Asset::Near);
+        let ledger_after_funding = contract.internal_treasury_balance(&Asset::Near);
+
+        set_context(&owner(), ZERO_TOKEN, (START + 10) * 1000);
+        contract.internal_cancel_stream(0);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), ledger_after_funding + 100 * RATE);
+        assert_eq!(contract.get_stream_owed(0), U128(0));
+    }
+
+    #[test]
+    fn failed_near_withdrawal_can_be_retried() {
+        let mut contract = setup_stream(Asset::Near);
+        assert_eq!(withdraw(&mut contract, START + 30), 30 * RATE);
+        assert_eq!(near_transfers(), vec![(account("bob"), 30 * RATE)]);
+        assert_eq!(event_count("stream_withdrawn"), 0);
+
+        set_callback_context(false, (START + 30) * 1000);
+        assert!(!contract.on_stream_withdraw(0, U128(30 * RATE)));
+        assert_eq!(contract.get_stream(0).unwrap().withdrawn, U128(0));
+        assert_eq!(event_count("stream_withdrawn"), 0);
+        assert_eq!(withdraw(&mut contract, START + 30), 30 * RATE);
+
+        set_callback_context(true, (START + 30) * 1000);
+        assert!(contract.on_stream_withdraw(0, U128(30 * RATE)));
+        assert_eq!(event_count("stream_withdrawn"), 1);
+    }
+}
@@ -0,0 +1,137 @@
+// Gemeinsame Hilfsfunktionen für die Unit-Tests der einzelnen Module
+use near_sdk::mock::MockAction;
+use near_sdk::test_utils::{get_created_receipts, get_logs, VMContextBuilder};
+use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig};
+
+use crate::proposal::{Proposal, ProposalKind, ProposalStatus};
+use crate::*;
+
+pub(crate) const ONE_TOKEN: u128 = 10u128.pow(24);
+pub(crate) const ONE_YOCTO: NearToken = NearToken::from_yoctonear(1);
+/// Blockzeit (ms) zu Beginn jedes Tests
+pub(crate) const START_MS: u64 = 1_700_000_000_000;
+
+pub(crate) fn account(name: &str) -> AccountId {
+    format!("{}.testnet", name).parse().unwrap()
+}
+
+pub(crate) fn dao() -> AccountId {
+    account("dao")
+}
+
+pub(crate) fn owner() -> AccountId {
+    account("owner")
+}
+
+pub(crate) fn treasury() -> AccountId {
+    account("treasury")
+}
+
+pub(crate) fn team() -> AccountId {
+    account("team")
+}
+
+fn context(predecessor: &AccountId, deposit: NearToken, now_ms: u64) -> VMContextBuilder {
+    let mut builder = VMContextBuilder::new();
+    builder
+        .current_account_id(dao())
+        .predecessor_account_id(predecessor.clone())
+        .signer_account_id(predecessor.clone())
+        .attached_deposit(deposit)
+        .account_balance(NearToken::from_near(1_000))
+        .block_timestamp(now_ms * 1_000_000);
+    builder
+}
+
+/// Setzt Aufrufer, Deposit und Blockzeit (ms) für die folgenden Aufrufe; der Storage bleibt erhalten
+pub(crate) fn set_context(predecessor: &AccountId, deposit: NearToken, now_ms: u64) {
+    testing_env!(context(predecessor, deposit, now_ms).build());
+}
+
+/// Wie set_context, mit rohem Input (z.B. Wasm-Code für stage_upgrade_code)
+pub(crate) fn set_input_context(predecessor: &AccountId, deposit: NearToken, now_ms: u64, input: &[u8]) {
+    let mut context = context(predecessor, deposit, now_ms).build();
+    context.input = input.into();
+    testing_env!(context);
+}
+
+/// Kontext für einen privaten Callback mit dem Ergebnis des vorherigen Receipts
+pub(crate) fn set_callback_context(succeeded: bool, now_ms: u64) {
+    let result = if succeeded {
+        PromiseResult::Successful(vec![])
+    } else {
+        PromiseResult::Failed
+    };
+    testing_env!(
+        context(&dao(), ZERO_TOKEN, now_ms).build(),
+        near_sdk::test_vm_config(),
+        RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![result],
+    );
+}
+
+/// NEAR-Überweisungen (Empfänger, yoctoNEAR), die seit dem letzten Kontextwechsel erzeugt wurden
+pub(crate) fn near_transfers() -> Vec<(AccountId, u128)> {
+    get_created_receipts()
+        .into_iter()
+        .flat_map(|receipt| {
+            let receiver_id = receipt.receiver_id.clone();
+            receipt.actions.into_iter().filter_map(move |action| match action {
+                MockAction::Transfer { deposit, .. } => Some((receiver_id.clone(), deposit.as_yoctonear())),
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+/// Anzahl der geloggten DAO-Events mit diesem Namen seit dem letzten Kontextwechsel
+pub(crate) fn event_count(event: &str) -> usize {
+    let needle = format!("\"event\":\"{}\"", event);
+    get_logs().iter().filter(|log| log.contains(&needle)).count()
+}
+
+/// Legt ein angenommenes, noch nicht ausgeführtes Proposal an
+pub(crate) fn insert_accepted_proposal(contract: &mut Contract, kind: ProposalKind, category: Option<&str>) -> u64 {
+    let id = contract.next_proposal_id;
+    let proposal = Proposal {
+        id,
+        title: "Test".to_string(),
+        description: String::new(),
+        created_at: 0,
+        proposer: owner().to_string(),
+        executed: false,
+        votes_for: vec![],
+        votes_against: vec![],
+        voted_accounts: vec![],
+        status: ProposalStatus::Accepted,
+        link: None,
+        tags: vec![],
+        amount: None,
+        target_account: None,
+        category: category.map(str::to_string),
+        deadline: None,
+        required_role: None,
+        quorum: None,
+        kind,
+        bond: U128(0),
+    };
+    contract.proposals.insert(&id, &proposal);
+    contract.proposal_ids.push(&id);
+    contract.next_proposal_id += 1;
+    id
+}
+
+/// Contract mit Owner, Treasury und Team (Standard-Metadaten, 10M Tokens, davon 3M in der Treasury)
+pub(crate) fn setup() -> Contract {
+    set_context(&owner(), ZERO_TOKEN, START_MS);
+    Contract::new_default_meta(owner(), U128(10_000_000 * ONE_TOKEN), treasury(), team())
+}
+
+/// Registriert den Account bei Bedarf und überweist ganze Tokens aus der Treasury
+pub(crate) fn fund(contract: &mut Contract, account_id: &AccountId, tokens: u128) {
+    if contract.accounts.get(account_id).is_none() {
+        contract.internal_register_account(account_id);
+    }
+    contract.internal_transfer(&treasury(), account_id, NearToken::from_yoctonear(tokens * ONE_TOKEN), None);
+}
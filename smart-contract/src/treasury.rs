@@ -0,0 +1,394 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{ext_contract, log, Gas, PromiseError, PromiseOrValue};
+use schemars::JsonSchema;
+
+use crate::*;
+
+const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
+const GAS_FOR_TREASURY_CALLBACK: Gas = Gas::from_tgas(10);
+
+/// Vermögenswert im Treasury-Ledger: natives NEAR oder ein NEP-141 Token (per Token-Contract)
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[schemars(crate = "schemars")]
+pub enum Asset {
+    Near,
+    Ft {
+        #[schemars(with = "String")]
+        token_id: AccountId,
+    },
+}
+
+/// Ob der vorherige Receipt (Transfer, ft_transfer, Deploy) erfolgreich war. Der Rückgabewert
+/// selbst wird nicht gelesen, damit ein grosser Wert kein Gas verbraucht.
+pub(crate) fn is_promise_success() -> bool {
+    !matches!(env::promise_result_checked(0, 0), Err(PromiseError::Failed))
+}
+
+#[ext_contract(ext_nep141)]
+pub trait Nep141 {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+impl Contract {
+    pub(crate) fn internal_treasury_balance(&self, asset: &Asset) -> u128 {
+        self.treasury_holdings.get(asset).unwrap_or(0)
+    }
+
+    pub(crate) fn internal_treasury_credit(&mut self, asset: &Asset, amount: u128) {
+        let balance = self
+            .internal_treasury_balance(asset)
+            .checked_add(amount)
+            .unwrap_or_else(|| DaoError::TreasuryBalanceOverflow.panic_with(format!("{:?}", asset)));
+        self.treasury_holdings.insert(asset, &balance);
+    }
+
+    /// Bucht Mittel aus dem Ledger ab. NEAR nur bis zum auszahlbaren Betrag (Reserve für Storage).
+    pub(crate) fn internal_treasury_debit(&mut self, asset: &Asset, amount: u128) {
+        if *asset == Asset::Near {
+            require!(
+                amount <= self.internal_spendable_near(),
+                DaoError::StorageReserveViolation.to_string()
+            );
+        }
+        let balance = self
+            .internal_treasury_balance(asset)
+            .checked_sub(amount)
+            .unwrap_or_else(|| DaoError::InsufficientTreasuryFunds.panic_with(format!("{:?}", asset)));
+        self.treasury_holdings.insert(asset, &balance);
+    }
+
+    /// DAO-Tokens werden über interne Guthaben verbucht, alle anderen Assets über den Treasury-Ledger
+    pub(crate) fn is_dao_token(asset: &Asset) -> bool {
+        matches!(asset, Asset::Ft { token_id } if *token_id == env::current_account_id())
+    }
+
+    /// Bucht DAO-Tokens von einem Account in den Escrow. Das Event nennt den Contract-Account
+    /// als Empfänger, damit die Summe aller Guthaben im Indexer dem Total Supply entspricht.
+    fn internal_escrow_deposit(&mut self, sender_id: &AccountId, amount: u128, memo: String) {
+        require!(amount > 0, DaoError::NonPositiveAmount.to_string());
+        self.internal_withdraw(sender_id, NearToken::from_yoctonear(amount));
+        self.escrow_tokens = self
+            .escrow_tokens
+            .checked_add(NearToken::from_yoctonear(amount))
+            .unwrap_or_else(|| DaoError::BalanceOverflow.panic_with(env::current_account_id()));
+        self.internal_emit_escrow_transfer(sender_id, &env::current_account_id(), amount, memo);
+    }
+
+    /// Zahlt DAO-Tokens aus dem Escrow an einen Account aus (registriert ihn bei Bedarf)
+    fn internal_escrow_withdraw(&mut self, receiver_id: &AccountId, amount: u128, memo: String) {
+        require!(amount > 0, DaoError::NonPositiveAmount.to_string());
+        self.escrow_tokens = self
+            .escrow_tokens
+            .checked_sub(NearToken::from_yoctonear(amount))
+            .unwrap_or_else(|| DaoError::InsufficientBalance.panic_with(env::current_account_id()));
+        if self.accounts.get(receiver_id).is_none() {
+            self.internal_register_account(receiver_id);
+        }
+        self.internal_deposit(receiver_id, NearToken::from_yoctonear(amount));
+        self.internal_emit_escrow_transfer(&env::current_account_id(), receiver_id, amount, memo);
+    }
+
+    fn internal_emit_escrow_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: u128,
+        memo: String,
+    ) {
+        FtTransfer {
+            old_owner_id: sender_id,
+            new_owner_id: receiver_id,
+            amount: &NearToken::from_yoctonear(amount),
+            memo: Some(&memo),
+        }
+        .emit();
+        self.internal_record_activity(ActivityKind::Transfer, sender_id, Some(receiver_id), Some(amount), Some(memo));
+    }
+
+    /// Reserviert Mittel aus der Treasury für Streams/Grants/Bounties. DAO-Tokens gehen in den
+    /// Escrow, andere Assets werden im Ledger abgebucht.
+    pub(crate) fn internal_reserve_from_treasury(&mut self, asset: &Asset, amount: u128, memo: String) {
+        if Self::is_dao_token(asset) {
+            let treasury_account_id = self.treasury_account_id();
+            self.internal_escrow_deposit(&treasury_account_id, amount, memo);
+        } else {
+            self.internal_treasury_debit(asset, amount);
+        }
+    }
+
+    /// Gibt reservierte, nicht ausgezahlte Mittel an die Treasury zurück
+    pub(crate) fn internal_return_to_treasury(&mut self, asset: &Asset, amount: u128, memo: String) {
+        if Self::is_dao_token(asset) {
+            let treasury_account_id = self.treasury_account_id();
+            self.internal_escrow_withdraw(&treasury_account_id, amount, memo);
+        } else {
+            self.internal_treasury_credit(asset, amount);
+        }
+    }
+
+    /// Zahlt reservierte Mittel aus. DAO-Tokens werden sofort übertragen (None),
+    /// für NEAR und NEP-141 wird die Überweisung als Promise zurückgegeben.
+    pub(crate) fn internal_pay_reserved(
+        &mut self,
+        asset: &Asset,
+        receiver_id: &AccountId,
+        amount: u128,
+        memo: String,
+    ) -> Option<Promise> {
+        match asset {
+            asset if Self::is_dao_token(asset) => {
+                self.internal_escrow_withdraw(receiver_id, amount, memo);
+                None
+            }
+            Asset::Near => Some(Promise::new(receiver_id.clone()).transfer(NearToken::from_yoctonear(amount))),
+            Asset::Ft { token_id } => Some(
+                ext_nep141::ext(token_id.clone())
+                    .with_attached_deposit(NearToken::from_yoctonear(1))
+                    .with_static_gas(GAS_FOR_FT_TRANSFER)
+                    .ft_transfer(receiver_id.clone(), U128(amount), Some(memo)),
+            ),
+        }
+    }
+
+    /// NEAR, die ausgezahlt werden können: verbuchter Treasury-Bestand, begrenzt durch das
+    /// Contract-Guthaben abzüglich der Reserve für den belegten Storage.
+    pub(crate) fn internal_spendable_near(&self) -> u128 {
+        let storage_reserve = env::storage_byte_cost()
+            .saturating_mul(env::storage_usage().into())
+            .as_yoctonear();
+        let available = env::account_balance().as_yoctonear().saturating_sub(storage_reserve);
+        std::cmp::min(self.internal_treasury_balance(&Asset::Near), available)
+    }
+
+    /// Auszahlung aus dem Treasury-Ledger. Der Betrag wird sofort abgebucht und im
+    /// Callback bei einem Fehlschlag wieder gutgeschrieben.
+    pub(crate) fn internal_treasury_payout(
+        &mut self,
+        proposal_id: u64,
+        asset: Asset,
+        receiver_id: AccountId,
+        amount: u128,
+    ) -> Promise {
+        require!(amount > 0, DaoError::NonPositiveAmount.to_string());
+        self.internal_treasury_debit(&asset, amount);
+
+        let transfer = match &asset {
+            Asset::Near => Promise::new(receiver_id).transfer(NearToken::from_yoctonear(amount)),
+            Asset::Ft { token_id } => ext_nep141::ext(token_id.clone())
+                .with_attached_deposit(NearToken::from_yoctonear(1))
+                .with_static_gas(GAS_FOR_FT_TRANSFER)
+                .ft_transfer(receiver_id, U128(amount), Some(format!("Proposal #{}", proposal_id))),
+        };
+        transfer.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_TREASURY_CALLBACK)
+                .on_treasury_payout(proposal_id, asset, U128(amount)),
+        )
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Spende in NEAR an die DAO-Treasury
+    #[payable]
+    pub fn donate(&mut self) {
+        let deposit = env::attached_deposit();
+        require!(deposit.gt(&ZERO_TOKEN), DaoError::ZeroDeposit.to_string());
+        self.internal_treasury_credit(&Asset::Near, deposit.as_yoctonear());
+        TreasuryDeposit {
+            asset: &Asset::Near,
+            sender: &env::predecessor_account_id(),
+            amount: U128(deposit.as_yoctonear()),
+        }
+        .emit();
+    }
+
+    /// NEP-141 Empfänger: eingehende Tokens anderer Contracts werden der Treasury gutgeschrieben
+    pub fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> PromiseOrValue<U128> {
+        let token_id = env::predecessor_account_id();
+        require!(
+            token_id != env::current_account_id(),
+            DaoError::DaoTokenDeposit.to_string()
+        );
+        log!("Treasury received {} of {} from {} ({})", amount.0, token_id, sender_id, msg);
+        let asset = Asset::Ft { token_id };
+        self.internal_treasury_credit(&asset, amount.0);
+        TreasuryDeposit {
+            asset: &asset,
+            sender: &sender_id,
+            amount,
+        }
+        .emit();
+        PromiseOrValue::Value(U128(0))
+    }
+
+    /// Callback nach einer Auszahlung: setzt das Proposal auf Executed bzw. Failed, stellt den
+    /// Ledger-Bestand bei einem Fehlschlag wieder her und meldet erst jetzt das endgültige Ergebnis.
+    #[private]
+    pub fn on_treasury_payout(&mut self, proposal_id: u64, asset: Asset, amount: U128) -> bool {
+        let succeeded = is_promise_success();
+        if !succeeded {
+            self.internal_treasury_credit(&asset, amount.0);
+            env::log_str(&format!("Treasury payout for proposal #{} failed, funds restored", proposal_id));
+        }
+        if let Some(mut proposal) = self.proposals.get(&proposal_id) {
+            if let (false, Some(category)) = (succeeded, proposal.category.as_deref()) {
+                self.internal_budget_refund(category, &asset, amount.0);
+            }
+            proposal.status = if succeeded {
+                ProposalStatus::Executed
+            } else {
+                ProposalStatus::Failed
+            };
+            self.proposals.insert(&proposal_id, &proposal);
+
+            ProposalPayoutResolved {
+                proposal_id,
+                asset: &asset,
+                amount,
+                status: &proposal.status,
+            }
+            .emit();
+            if succeeded {
+                ProposalExecuted {
+                    proposal_id,
+                    kind: &proposal.kind,
+                }
+                .emit();
+            }
+        }
+        succeeded
+    }
+
+    /// NEAR, die aktuell per Proposal ausgezahlt werden können
+    pub fn get_spendable_near(&self) -> U128 {
+        U128(self.internal_spendable_near())
+    }
+
+    /// Alle Bestände der Treasury (NEAR und NEP-141)
+    pub fn get_treasury_holdings(&self) -> Vec<(Asset, U128)> {
+        self.treasury_holdings
+            .iter()
+            .map(|(asset, balance)| (asset, U128(balance)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proposal::ProposalKind;
+    use crate::test_utils::*;
+
+    fn usdc() -> Asset {
+        Asset::Ft { token_id: account("usdc") }
+    }
+
+    fn receive_usdc(contract: &mut Contract, amount: u128) {
+        set_context(&account("usdc"), ZERO_TOKEN, START_MS);
+        let _ = contract.ft_on_transfer(account("bob"), U128(amount), String::new());
+    }
+
+    fn execute_usdc_payout(contract: &mut Contract, amount: u128) -> u64 {
+        let proposal_id = insert_accepted_proposal(
+            contract,
+            ProposalKind::TreasuryTransfer { asset: usdc(), receiver_id: account("carol"), amount: U128(amount) },
+            None,
+        );
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.internal_execute_proposal(proposal_id);
+        proposal_id
+    }
+
+    #[test]
+    fn payout_is_reported_only_from_the_callback() {
+        let mut contract = setup();
+        receive_usdc(&mut contract, 500);
+        let proposal_id = execute_usdc_payout(&mut contract, 200);
+
+        let proposal = contract.get_proposal_by_id(proposal_id).unwrap();
+        assert!(proposal.executed);
+        assert_eq!(proposal.status, ProposalStatus::Accepted);
+        assert_eq!(event_count("proposal_executed"), 0);
+        assert_eq!(contract.internal_treasury_balance(&usdc()), 300);
+
+        set_callback_context(true, START_MS);
+        assert!(contract.on_treasury_payout(proposal_id, usdc(), U128(200)));
+        assert_eq!(contract.get_proposal_by_id(proposal_id).unwrap().status, ProposalStatus::Executed);
+        assert_eq!(event_count("proposal_payout_resolved"), 1);
+        assert_eq!(event_count("proposal_executed"), 1);
+        assert_eq!(contract.internal_treasury_balance(&usdc()), 300);
+    }
+
+    #[test]
+    fn failed_payout_restores_ledger() {
+        let mut contract = setup();
+        receive_usdc(&mut contract, 500);
+        let proposal_id = execute_usdc_payout(&mut contract, 200);
+
+        set_callback_context(false, START_MS);
+        assert!(!contract.on_treasury_payout(proposal_id, usdc(), U128(200)));
+        assert_eq!(contract.get_proposal_by_id(proposal_id).unwrap().status, ProposalStatus::Failed);
+        assert_eq!(event_count("proposal_payout_resolved"), 1);
+        assert_eq!(event_count("proposal_executed"), 0);
+        assert_eq!(contract.internal_treasury_balance(&usdc()), 500);
+    }
+
+    fn execute_near_payout(contract: &mut Contract, amount: u128, category: Option<&str>) -> u64 {
+        let proposal_id = insert_accepted_proposal(
+            contract,
+            ProposalKind::NearTransfer { receiver_id: account("carol"), amount: U128(amount) },
+            category,
+        );
+        set_context(&owner(), ZERO_TOKEN, START_MS);
+        contract.internal_execute_proposal(proposal_id);
+        proposal_id
+    }
+
+    #[test]
+    fn near_payout_is_final_after_the_callback() {
+        let mut contract = setup();
+        set_context(&account("bob"), NearToken::from_near(10), START_MS);
+        contract.donate();
+        let amount = NearToken::from_near(4).as_yoctonear();
+        let proposal_id = execute_near_payout(&mut contract, amount, None);
+        assert_eq!(contract.get_proposal_by_id(proposal_id).unwrap().status, ProposalStatus::Accepted);
+        assert_eq!(event_count("proposal_executed"), 0);
+
+        set_callback_context(true, START_MS);
+        assert!(contract.on_treasury_payout(proposal_id, Asset::Near, U128(amount)));
+        assert_eq!(contract.get_proposal_by_id(proposal_id).unwrap().status, ProposalStatus::Executed);
+        assert_eq!(event_count("proposal_executed"), 1);
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), NearToken::from_near(6).as_yoctonear());
+    }
+
+    #[test]
+    fn failed_near_payout_refunds_ledger_and_budget() {
+        let mut contract = setup();
+        set_context(&account("bob"), NearToken::from_near(10), START_MS);
+        contract.donate();
+        let budget = NearToken::from_near(5).as_yoctonear();
+        contract.internal_set_budget("dev".to_string(), Asset::Near, U128(budget), 30 * 24 * 60 * 60, false);
+        let amount = NearToken::from_near(4).as_yoctonear();
+        let proposal_id = execute_near_payout(&mut contract, amount, Some("dev"));
+        assert_eq!(contract.get_budget("dev".to_string(), Asset::Near).unwrap().spent, U128(amount));
+
+        set_callback_context(false, START_MS);
+        assert!(!contract.on_treasury_payout(proposal_id, Asset::Near, U128(amount)));
+        assert_eq!(contract.get_proposal_by_id(proposal_id).unwrap().status, ProposalStatus::Failed);
+        assert_eq!(event_count("proposal_payout_resolved"), 1);
+        assert_eq!(contract.get_budget("dev".to_string(), Asset::Near).unwrap().spent, U128(0));
+        assert_eq!(contract.internal_treasury_balance(&Asset::Near), NearToken::from_near(10).as_yoctonear());
+    }
+
+    #[test]
+    #[should_panic(expected = "StorageReserveViolation")]
+    fn near_reservations_respect_storage_reserve() {
+        let mut contract = setup();
+        // Ledger-Bestand über dem tatsächlichen Contract-Guthaben (1000 NEAR im Testkontext)
+        contract.internal_treasury_credit(&Asset::Near, NearToken::from_near(2_000).as_yoctonear());
+        contract.internal_reserve_from_treasury(&Asset::Near, NearToken::from_near(1_000).as_yoctonear(), "Stream".to_string());
+    }
+}
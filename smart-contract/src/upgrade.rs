@@ -0,0 +1,219 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::Gas;
+
+use crate::pause::Subsystem;
+use crate::treasury::is_promise_success;
+use crate::*;
+
+/// Standard-Wartezeit zwischen angenommenem Upgrade-Proposal und Deployment
+pub const DEFAULT_UPGRADE_TIMELOCK_SEC: u64 = 2 * 24 * 60 * 60; // 2 Tage
+const GAS_FOR_MIGRATE: Gas = Gas::from_tgas(100);
+const GAS_FOR_UPGRADE_CALLBACK: Gas = Gas::from_tgas(10);
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, PartialEq, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub enum UpgradeStatus {
+    /// Wartet auf den Ablauf des Timelocks
+    Scheduled,
+    /// Deploy + migrate() laufen, Ergebnis kommt per Callback
+    Deploying,
+    Deployed,
+    Failed,
+}
+
+/// Per Proposal beschlossenes Upgrade (Zeitangaben in Sekunden)
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, NearSchema, Debug, Clone)]
+#[borsh(crate = "near_sdk::borsh")]
+#[serde(crate = "near_sdk::serde")]
+pub struct ScheduledUpgrade {
+    pub proposal_id: u64,
+    pub code_hash: Base58CryptoHash,
+    pub executable_at: u64,
+    pub status: UpgradeStatus,
+}
+
+impl Contract {
+    /// Wird bei der Ausführung eines UpgradeContract-Proposals aufgerufen: plant das Upgrade
+    /// mit Timelock ein. Das Deployment erfolgt danach über apply_upgrade.
+    pub(crate) fn internal_schedule_upgrade(&mut self, proposal_id: u64, code_hash: Base58CryptoHash) {
+        require!(
+            self.upgrade_code.contains_key(&code_hash),
+            DaoError::UpgradeCodeNotStaged.with_detail(String::from(&code_hash))
+        );
+        if let Some(upgrade) = self.upgrade.as_ref() {
+            require!(
+                upgrade.status != UpgradeStatus::Deploying,
+                DaoError::UpgradeInProgress.to_string()
+            );
+        }
+        self.upgrade = Some(ScheduledUpgrade {
+            proposal_id,
+            code_hash,
+            executable_at: env::block_timestamp_ms() / 1000 + self.config.upgrade_timelock_sec,
+            status: UpgradeStatus::Scheduled,
+        });
+        UpgradeStatusChanged {
+            proposal_id,
+            code_hash: &code_hash,
+            status: &UpgradeStatus::Scheduled,
+        }
+        .emit();
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Hinterlegt neuen Wasm-Code (roher Input, kein JSON) und gibt dessen sha256-Hash zurück.
+    /// Nur für Accounts, die Proposals erstellen dürfen. Der Deposit muss den belegten Storage
+    /// decken, der Rest wird erstattet (bei bereits hinterlegtem Code der gesamte Deposit).
+    #[payable]
+    pub fn stage_upgrade_code(&mut self) -> Base58CryptoHash {
+        let caller = env::predecessor_account_id();
+        self.require_permission(&caller, Permission::CreateProposal);
+        let code = env::input().unwrap_or_else(|| DaoError::MissingUpgradeCode.panic());
+        require!(!code.is_empty(), DaoError::MissingUpgradeCode.to_string());
+        let code_hash = Base58CryptoHash::from(env::sha256_array(&code));
+        if self.upgrade_code.contains_key(&code_hash) {
+            let deposit = env::attached_deposit();
+            if deposit.gt(&ZERO_TOKEN) {
+                Promise::new(caller).transfer(deposit).detach();
+            }
+            return code_hash;
+        }
+
+        let initial_storage = env::storage_usage();
+        self.upgrade_code.insert(&code_hash, &code);
+        let storage_cost = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage() - initial_storage).into());
+        let deposit = env::attached_deposit();
+        require!(
+            deposit >= storage_cost,
+            DaoError::InsufficientStorageDeposit.with_detail(storage_cost.as_yoctonear())
+        );
+        let refund = deposit.saturating_sub(storage_cost);
+        if refund.gt(&ZERO_TOKEN) {
+            Promise::new(caller.clone()).transfer(refund).detach();
+        }
+        UpgradeCodeStaged {
+            code_hash: &code_hash,
+            staged_by: &caller,
+            storage_cost: U128(storage_cost.as_yoctonear()),
+        }
+        .emit();
+        code_hash
+    }
+
+    /// Deployt den beschlossenen Code nach Ablauf des Timelocks und ruft migrate() auf
+    pub fn apply_upgrade(&mut self) {
+        self.require_permission(&env::predecessor_account_id(), Permission::Execute);
+        self.assert_not_paused(Subsystem::GovernanceExecution);
+        let mut upgrade = self
+            .upgrade
+            .clone()
+            .unwrap_or_else(|| DaoError::NoUpgradeScheduled.panic());
+        require!(
+            upgrade.status == UpgradeStatus::Scheduled,
+            DaoError::NoUpgradeScheduled.with_detail(format!("{:?}", upgrade.status))
+        );
+        require!(
+            env::block_timestamp_ms() / 1000 >= upgrade.executable_at,
+            DaoError::UpgradeTimelockActive.with_detail(upgrade.executable_at)
+        );
+        let code = self
+            .upgrade_code
+            .get(&upgrade.code_hash)
+            .unwrap_or_else(|| DaoError::UpgradeCodeNotStaged.panic_with(String::from(&upgrade.code_hash)));
+
+        upgrade.status = UpgradeStatus::Deploying;
+        self.upgrade = Some(upgrade.clone());
+        UpgradeStatusChanged {
+            proposal_id: upgrade.proposal_id,
+            code_hash: &upgrade.code_hash,
+            status: &upgrade.status,
+        }
+        .emit();
+
+        // Deploy und migrate() laufen im selben Receipt: schlägt migrate() fehl, wird auch
+        // das Deployment zurückgerollt. Der Callback läuft bereits mit dem neuen Code.
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), b"{}".to_vec(), ZERO_TOKEN, GAS_FOR_MIGRATE)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_UPGRADE_CALLBACK)
+                    .on_upgrade(upgrade.proposal_id),
+            )
+            .detach();
+    }
+
+    /// Ergebnis des Upgrades; der hinterlegte Code wird nach Erfolg entfernt
+    #[private]
+    pub fn on_upgrade(&mut self, proposal_id: u64) -> bool {
+        let succeeded = is_promise_success();
+        if let Some(mut upgrade) = self.upgrade.clone().filter(|upgrade| upgrade.proposal_id == proposal_id) {
+            upgrade.status = if succeeded {
+                self.upgrade_code.remove(&upgrade.code_hash);
+                UpgradeStatus::Deployed
+            } else {
+                UpgradeStatus::Failed
+            };
+            UpgradeStatusChanged {
+                proposal_id,
+                code_hash: &upgrade.code_hash,
+                status: &upgrade.status,
+            }
+            .emit();
+            self.upgrade = Some(upgrade);
+        }
+        if !succeeded {
+            env::log_str(&format!("Upgrade from proposal #{} failed", proposal_id));
+        }
+        succeeded
+    }
+
+    pub fn get_upgrade(&self) -> Option<ScheduledUpgrade> {
+        self.upgrade.clone()
+    }
+
+    pub fn is_upgrade_code_staged(&self, code_hash: Base58CryptoHash) -> bool {
+        self.upgrade_code.contains_key(&code_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    const CODE: &[u8] = b"\0asm upgrade";
+
+    fn stage(contract: &mut Contract, caller: &AccountId, deposit: NearToken) -> Base58CryptoHash {
+        set_input_context(caller, deposit, START_MS, CODE);
+        contract.stage_upgrade_code()
+    }
+
+    #[test]
+    fn restaging_refunds_the_whole_deposit() {
+        let mut contract = setup();
+        let deposit = NearToken::from_near(1);
+        let code_hash = stage(&mut contract, &owner(), deposit);
+        assert!(contract.is_upgrade_code_staged(code_hash));
+        let refund = near_transfers()[0].1;
+        assert!(refund > 0 && refund < deposit.as_yoctonear());
+
+        assert_eq!(stage(&mut contract, &owner(), deposit), code_hash);
+        assert_eq!(near_transfers(), vec![(owner(), deposit.as_yoctonear())]);
+        assert_eq!(event_count("upgrade_code_staged"), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "MissingPermission")]
+    fn staging_requires_proposal_permission() {
+        let mut contract = setup();
+        contract.internal_register_account(&account("bob"));
+        stage(&mut contract, &account("bob"), NearToken::from_near(1));
+    }
+}
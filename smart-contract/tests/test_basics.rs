@@ -12,19 +12,24 @@ async fn test_basics_on(contract_wasm: &[u8]) -> Result<(), Box<dyn std::error::
     let sandbox = near_workspaces::sandbox().await?;
     let contract = sandbox.dev_deploy(contract_wasm).await?;
 
-    let user_account = sandbox.dev_create_account().await?;
-
-    let outcome = user_account
-        .call(contract.id(), "set_greeting")
-        .args_json(json!({"greeting": "Hello World!"}))
+    let owner = sandbox.dev_create_account().await?;
+    let treasury = sandbox.dev_create_account().await?;
+    let team = sandbox.dev_create_account().await?;
+
+    let outcome = contract
+        .call("new_default_meta")
+        .args_json(json!({
+            "owner_id": owner.id(),
+            "total_supply": "10000000000000000000000000000000",
+            "treasury_account_id": treasury.id(),
+            "team_account_id": team.id(),
+        }))
         .transact()
         .await?;
     assert!(outcome.is_success());
 
-    let user_message_outcome = contract.view("get_greeting").args_json(json!({})).await?;
-    assert_eq!(user_message_outcome.json::<String>()?, "Hello World!");
+    let metadata = contract.view("ft_metadata").args_json(json!({})).await?;
+    assert_eq!(metadata.json::<serde_json::Value>()?["symbol"], "THESISDAO");
 
     Ok(())
 }
-
-